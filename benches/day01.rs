@@ -0,0 +1,93 @@
+//! Compares the day 1 digit scanner with the implementation that rewrote every line, on a large generated input.
+//!
+//! Run with `cargo bench --bench day01 [<lines>]`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc_2023::bench::Stats;
+use aoc_2023::generate::{self, DEFAULT_SEED};
+use aoc_2023::puzzle01::{extract_number, vocabulary, Day01};
+use aoc_2023::report::milliseconds;
+use aoc_2023::solution::Part;
+
+const DEFAULT_LINES: usize = 100_000;
+const ITERATIONS: usize = 10;
+
+const NUMBER_NAMES: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+fn main() {
+    let lines = std::env::args().skip(1).find_map(|argument| argument.parse().ok()).unwrap_or(DEFAULT_LINES);
+    let input = generate::generate(&Day01, lines, DEFAULT_SEED).expect("day 1 has a generator");
+
+    let (scanner_total, scanner) = measure(|| input.lines().map(|line| extract_number(line, vocabulary(Part::Two)) as u64).sum());
+    let (rewriting_total, rewriting) = measure(|| input.lines().map(|line| rewriting_extract_number(line) as u64).sum());
+    assert_eq!(scanner_total, rewriting_total, "both implementations must give the same answer");
+
+    println!("day 1 part 2 on {} generated lines, {} runs", lines, ITERATIONS);
+    println!("{:<10} {:>12} {:>12}", "", "min ms", "median ms");
+    for (name, stats) in [("scanner", scanner), ("rewriting", rewriting)] {
+        println!("{:<10} {:>12} {:>12}", name, milliseconds(stats.min), milliseconds(stats.median));
+    }
+    println!("speedup    {:.1}x", rewriting.median.as_secs_f64() / scanner.median.as_secs_f64());
+}
+
+fn measure(f: impl Fn() -> u64) -> (u64, Stats) {
+    let mut total = 0;
+    let samples = (0..ITERATIONS).map(|_| {
+        let start = Instant::now();
+        total = black_box(f());
+        start.elapsed()
+    }).collect::<Vec<Duration>>();
+    (total, Stats::from_samples(&samples))
+}
+
+// the part 2 extraction before the scanner: replaces the first name from the start and the last from the end by
+// their digits, then takes the first and the last digit
+fn rewriting_extract_number(line: &str) -> i32 {
+    let line = replace_number_names(&replace_number_names(line, false), true);
+    let mut digits = line.chars().filter(char::is_ascii_digit);
+    match (digits.next(), digits.next_back()) {
+        (Some(first), Some(last)) => String::from_iter([first, last]).parse().unwrap(),
+        (Some(first), None) => String::from_iter([first, first]).parse().unwrap(),
+        _ => 0,
+    }
+}
+
+fn replace_number_names(line_ref: &str, reverse: bool) -> String {
+    let line = line_ref.to_string();
+
+    let mut base_range = 0..line.len();
+    let mut base_range_reverse = (0..line.len()).rev();
+    let range = if !reverse {
+        &mut base_range as &mut dyn Iterator<Item = _>
+    } else {
+        &mut base_range_reverse
+    };
+
+    for position in range {
+        let char = line.chars().nth(position);
+        if let Some(char) = char {
+            if char.is_ascii_digit() {
+                return line;
+            }
+        }
+
+        for (number, text) in NUMBER_NAMES.iter().enumerate() {
+            let start_offset: i32 = if reverse { position as i32 + 1 - text.len() as i32 } else { position as i32 };
+            if (!reverse && position + text.len() > line.len()) || (reverse && start_offset < 0) {
+                continue;
+            }
+            let (before, tmp) = line.split_at(start_offset as usize);
+            let (value, after) = tmp.split_at(text.len());
+
+            if value == *text {
+                let mut new_line = String::from(before);
+                new_line.push_str(&(number + 1).to_string());
+                new_line.push_str(after);
+                return new_line;
+            }
+        }
+    }
+    line
+}
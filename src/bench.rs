@@ -0,0 +1,150 @@
+use std::hint::black_box;
+use std::io;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use crate::error::SolveError;
+use crate::input::InputSource;
+use crate::report::milliseconds;
+use crate::solution::{Part, Puzzle};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        if samples.is_empty() {
+            return Stats { runs: 0, min: Duration::ZERO, median: Duration::ZERO, mean: Duration::ZERO, stddev: Duration::ZERO };
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) { (sorted[middle - 1] + sorted[middle]) / 2 } else { sorted[middle] };
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / sorted.len() as f64;
+        let variance = sorted.iter().map(|sample| (sample.as_secs_f64() - mean).powi(2)).sum::<f64>() / sorted.len() as f64;
+
+        Stats {
+            runs: sorted.len(),
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+// parses the input and solves the parts `iterations` times, timing each phase separately
+pub fn bench(puzzle: &dyn Puzzle, parts: &[Part], input: &str, iterations: usize) -> Result<Vec<Measurement>, SolveError> {
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut solve_samples = vec![Vec::with_capacity(iterations); parts.len()];
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let prepared = puzzle.prepare(black_box(input))?;
+        parse_samples.push(start.elapsed());
+
+        for (samples, part) in solve_samples.iter_mut().zip(parts) {
+            let start = Instant::now();
+            black_box(prepared.solve(*part)?);
+            samples.push(start.elapsed());
+        }
+    }
+
+    let mut measurements = vec![Measurement { day: puzzle.day(), phase: Phase::Parse, stats: Stats::from_samples(&parse_samples) }];
+    for (samples, part) in solve_samples.iter().zip(parts) {
+        measurements.push(Measurement { day: puzzle.day(), phase: Phase::Solve(*part), stats: Stats::from_samples(samples) });
+    }
+    Ok(measurements)
+}
+
+// benchmarks the inputs of `puzzles` as a table, returns whether every input could be read and solved
+pub fn bench_all<W: Write>(out: &mut W, puzzles: &[&dyn Puzzle], parts: &[Part], source: &InputSource, iterations: usize) -> io::Result<bool> {
+    let mut success = true;
+    write_header(out)?;
+    for puzzle in puzzles {
+        let measurements = source.read(puzzle.day()).map_err(|error| error.to_string())
+            .and_then(|input| bench(*puzzle, parts, &input, iterations).map_err(|error| error.to_string()));
+        match measurements {
+            Ok(measurements) => {
+                for measurement in &measurements {
+                    write_measurement(out, measurement)?;
+                }
+            }
+            Err(error) => {
+                eprintln!("error: {}", error);
+                success = false;
+            }
+        }
+    }
+    Ok(success)
+}
+
+pub fn write_header<W: Write>(out: &mut W) -> io::Result<()> {
+    writeln!(out, "{:>3} {:>4} {:<5} {:>6} {:>12} {:>12} {:>12} {:>12}", "day", "part", "phase", "runs", "min ms", "median ms", "mean ms", "stddev ms")
+}
+
+pub fn write_measurement<W: Write>(out: &mut W, measurement: &Measurement) -> io::Result<()> {
+    let (part, phase) = match measurement.phase {
+        Phase::Parse => ("-".to_string(), "parse"),
+        Phase::Solve(part) => (part.to_string(), "solve"),
+    };
+    let stats = &measurement.stats;
+    writeln!(out, "{:>3} {:>4} {:<5} {:>6} {:>12} {:>12} {:>12} {:>12}", measurement.day, part, phase, stats.runs,
+             milliseconds(stats.min), milliseconds(stats.median), milliseconds(stats.mean), milliseconds(stats.stddev))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle06::Day06;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1118);
+        assert_eq!(Stats::from_samples(&samples[..3]).median, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_bench() {
+        let measurements = bench(&Day06, &[Part::Two], "Time:      7  15   30\nDistance:  9  40  200", 3).unwrap();
+
+        assert_eq!(measurements.iter().map(|measurement| (measurement.phase, measurement.stats.runs)).collect::<Vec<_>>(),
+                   vec![(Phase::Parse, 3), (Phase::Solve(Part::Two), 3)]);
+    }
+
+    #[test]
+    fn test_table() {
+        let stats = Stats::from_samples(&[Duration::from_micros(1500)]);
+        let mut out = Vec::new();
+        write_measurement(&mut out, &Measurement { day: 5, phase: Phase::Solve(Part::Two), stats }).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "  5    2 solve      1        1.500        1.500        1.500        0.000\n");
+    }
+}
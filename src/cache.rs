@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::input::InputSource;
+use crate::runner;
+use crate::runner::Record;
+use crate::solution::{Answer, Part, Puzzle};
+
+pub const CACHE_FILE: &str = ".aoc-cache";
+
+/// Answers and timings of earlier runs, keyed by day, part and a hash of the input.
+///
+/// Every entry records the version of the binary that computed it, entries of other versions are dropped on load
+/// so that a rebuild never reports stale answers. The file has one `day part input-hash version answer parse-ns solve-ns`
+/// line per entry.
+#[derive(Debug)]
+pub struct Cache {
+    path: PathBuf,
+    version: String,
+    entries: HashMap<(u8, Part, u64), Entry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Entry {
+    answer: Answer,
+    parse_time: Duration,
+    solve_time: Duration,
+}
+
+#[derive(Debug)]
+pub struct CacheError {
+    path: PathBuf,
+    error: io::Error,
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot access cache '{}': {}", self.path.display(), self.error)
+    }
+}
+
+impl Cache {
+    // a missing or unreadable cache file starts an empty cache
+    pub fn load(path: &Path, version: &str) -> Cache {
+        let entries = fs::read_to_string(path).map(|content| {
+            content.lines().filter_map(|line| parse_entry(line, version)).collect()
+        }).unwrap_or_default();
+        Cache { path: path.to_path_buf(), version: version.to_string(), entries }
+    }
+
+    pub fn save(&self) -> Result<(), CacheError> {
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(key, _)| **key);
+        let content = entries.iter().map(|((day, part, hash), entry)| {
+            format!("{} {} {:016x} {} {} {} {}\n", day, part, hash, self.version, entry.answer, entry.parse_time.as_nanos(), entry.solve_time.as_nanos())
+        }).collect::<String>();
+        fs::write(&self.path, content).map_err(|error| CacheError { path: self.path.clone(), error })
+    }
+
+    pub fn clear(path: &Path) -> Result<(), CacheError> {
+        match fs::remove_file(path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(CacheError { path: path.to_path_buf(), error }),
+            _ => Ok(()),
+        }
+    }
+
+    fn get(&self, day: u8, part: Part, hash: u64) -> Option<&Entry> {
+        self.entries.get(&(day, part, hash))
+    }
+
+    // only solved parts are cached, failures are reported again on the next run
+    fn insert(&mut self, record: &Record, hash: u64) {
+        if let Ok(answer) = record.result {
+            self.entries.insert((record.day, record.part, hash), Entry { answer, parse_time: record.parse_time, solve_time: record.solve_time });
+        }
+    }
+}
+
+fn parse_entry(line: &str, version: &str) -> Option<((u8, Part, u64), Entry)> {
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+    let [day, part, hash, entry_version, answer, parse_time, solve_time] = fields[..] else { return None };
+    if entry_version != version {
+        return None;
+    }
+    let key = (day.parse().ok()?, Part::parse(part)?, u64::from_str_radix(hash, 16).ok()?);
+    let nanoseconds = |value: &str| value.parse().ok().map(Duration::from_nanos);
+    Some((key, Entry { answer: answer.parse().ok()?, parse_time: nanoseconds(parse_time)?, solve_time: nanoseconds(solve_time)? }))
+}
+
+/// Identifies the running binary by its package version and the size and modification time of the executable.
+pub fn binary_version() -> String {
+    let stamp = std::env::current_exe().and_then(fs::metadata).ok()
+        .and_then(|metadata| Some((metadata.len(), metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos())));
+    match stamp {
+        Some((length, modified)) => format!("{}+{:016x}", env!("CARGO_PKG_VERSION"), fnv1a(format!("{}-{}", length, modified).as_bytes())),
+        None => env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+// 64 bit FNV-1a, stable across builds and platforms unlike the std hashers
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Like [`runner::run_all`], but takes the records of days whose input is unchanged from the cache and adds the new ones.
+/// Stdin can only be read once, so it is never cached.
+pub fn run_all(cache: &mut Cache, puzzles: &[&dyn Puzzle], parts: &[Part], source: &InputSource, jobs: usize) -> Vec<Record> {
+    if *source == InputSource::Stdin {
+        return runner::run_all(puzzles, parts, source, jobs);
+    }
+
+    // every input is read once, the missing days are solved on the very content that was hashed
+    let mut cached = HashMap::new();
+    let mut missing = Vec::new();
+    let mut hashes = HashMap::new();
+    for puzzle in puzzles {
+        let day = puzzle.day();
+        let input = match source.read(day) {
+            Ok(input) => input,
+            Err(error) => {
+                missing.push((*puzzle, Err(error.to_string())));
+                continue;
+            }
+        };
+        let hash = fnv1a(input.as_bytes());
+        let entries = parts.iter().map(|part| cache.get(day, *part, hash).map(|entry| (*part, *entry))).collect::<Option<Vec<_>>>();
+        match entries {
+            Some(entries) => {
+                cached.insert(day, entries);
+            }
+            None => {
+                hashes.insert(day, hash);
+                missing.push((*puzzle, Ok(input)));
+            }
+        }
+    }
+
+    let mut computed = runner::run_inputs(&missing, parts, source, jobs).into_iter().peekable();
+    let mut records = Vec::new();
+    for puzzle in puzzles {
+        let day = puzzle.day();
+        match cached.get(&day) {
+            Some(entries) => records.extend(entries.iter().map(|(part, entry)| Record {
+                day, part: *part, input: source.name(day), parse_time: entry.parse_time, solve_time: entry.solve_time, result: Ok(entry.answer),
+            })),
+            None => while let Some(record) = computed.next_if(|record| record.day == day) {
+                if let Some(hash) = hashes.get(&day) {
+                    cache.insert(&record, *hash);
+                }
+                records.push(record);
+            },
+        }
+    }
+    records
+}
+
+// `run_all` with the cache file of the working directory, a cache that cannot be written only costs the next run its
+// speed up, so that is just a warning
+pub fn run_all_cached(puzzles: &[&dyn Puzzle], parts: &[Part], source: &InputSource, jobs: usize) -> Vec<Record> {
+    let mut cache = Cache::load(Path::new(CACHE_FILE), &binary_version());
+    let records = run_all(&mut cache, puzzles, parts, source, jobs);
+    if let Err(error) = cache.save() {
+        eprintln!("warning: {}", error);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle06::{Day06, EXAMPLES};
+    use crate::testing::temp_directory;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let directory = temp_directory("cache-round-trip");
+        let path = directory.join(CACHE_FILE);
+        let record = Record { day: 6, part: Part::One, input: String::from("-"), parse_time: Duration::from_nanos(1500), solve_time: Duration::from_nanos(20), result: Ok(288) };
+
+        let mut cache = Cache::load(&path, "1");
+        cache.insert(&record, 42);
+        cache.insert(&Record { part: Part::Two, result: Err(String::from("broken")), ..record.clone() }, 42);
+        cache.save().unwrap();
+
+        let cache = Cache::load(&path, "1");
+        assert_eq!(cache.get(6, Part::One, 42), Some(&Entry { answer: 288, parse_time: Duration::from_nanos(1500), solve_time: Duration::from_nanos(20) }));
+        assert_eq!(cache.get(6, Part::Two, 42), None);
+        assert_eq!(Cache::load(&path, "2").get(6, Part::One, 42), None);
+
+        Cache::clear(&path).unwrap();
+        Cache::clear(&path).unwrap();
+        assert!(!path.exists());
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_run_all_uses_cached_answers() {
+        let directory = temp_directory("cache-run");
+        fs::write(directory.join("06_data.txt"), EXAMPLES[0].input).unwrap();
+        let source = InputSource::Directory(directory.clone());
+        let mut cache = Cache::load(&directory.join(CACHE_FILE), "1");
+
+        let records = run_all(&mut cache, &[&Day06], &Part::ALL, &source, 1);
+        assert_eq!(records.iter().map(|record| record.result.clone()).collect::<Vec<_>>(), vec![Ok(288), Ok(71503)]);
+
+        // a faked entry shows that the second run does not solve again
+        let hash = fnv1a(EXAMPLES[0].input.as_bytes());
+        cache.insert(&Record { result: Ok(1), ..records[0].clone() }, hash);
+        let records = run_all(&mut cache, &[&Day06], &Part::ALL, &source, 1);
+        assert_eq!(records.iter().map(|record| record.result.clone()).collect::<Vec<_>>(), vec![Ok(1), Ok(71503)]);
+
+        fs::write(directory.join("06_data.txt"), "Time: 7\nDistance: 9").unwrap();
+        let records = run_all(&mut cache, &[&Day06], &[Part::One], &source, 1);
+        assert_eq!(records[0].result, Ok(4));
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
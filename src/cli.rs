@@ -0,0 +1,555 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_2023::generate::{DEFAULT_SEED, DEFAULT_SIZE};
+use aoc_2023::input::InputSource;
+use aoc_2023::report::Format;
+use aoc_2023::solution::Part;
+
+pub(crate) const USAGE: &str = concat!(r#"usage: aoc-2023 [<command>] [<options>]
+
+commands:
+  run     run puzzles (default)
+  bench   measure parsing and solving over repeated runs
+  verify  compare the answers with the expected answers
+  watch   re-run a single day whenever its input or example files change
+  repl    evaluate lines read from stdin one at a time for a single day
+  generate
+          write a random input of a single day for stress tests
+  new-day <day>
+          generate and register the module of a new day
+  cache clear
+          remove the cached answers
+  list    list available days
+  help    show this message
+
+options:
+  --day <days>    days to run, e.g. 5, 1-4 or 1,3,5-7 (may be repeated)
+  --part <part>   run only part 1 or part 2
+  --all           run all available days (default)
+  --input <file>  read the input of a single day from <file>, or from stdin for '-' (except watch)
+  --input-dir <dir>
+                  read the inputs from <dir>/NN_data.txt (default: $"#, "AOC_INPUT_DIR", r#" or .)
+  --format <format>
+                  run: output format, text (default), json or csv
+  --time          run: show parse and solve times in text output
+  --example       run: solve the examples of the puzzle descriptions instead of the inputs
+  --explain       run: list the items every answer adds up from, e.g. the value of each line
+  --no-cache      run, verify: solve every part again instead of reusing answers for unchanged inputs
+  --jobs <n>      run, verify: number of threads solving days and parts concurrently (default: 1)
+  --verbose, -v   run, verify, watch: write solver debug events to stderr, -vv adds trace events
+  --iterations <n>
+                  bench: number of repetitions (default: 10)
+  --answers <file>
+                  verify, watch: expected answers (default: answers.toml), watch runs unverified without the file
+  --interval <ms> watch: polling interval (default: 500)
+  --size <n>      generate: number of lines, games, cards, ... (default: 100), day 6 writes at most 4 races
+  --seed <n>      generate: seed of the random input, the same seed gives the same input (default: 2023)"#);
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command {
+    Run(RunOptions),
+    Bench(RunOptions, usize),
+    Verify(RunOptions, PathBuf),
+    Watch(RunOptions, PathBuf, Duration),
+    Repl(RunOptions),
+    Generate(RunOptions, usize, u64),
+    NewDay(u8),
+    ClearCache,
+    List,
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct RunOptions {
+    pub(crate) days: Vec<u8>,
+    pub(crate) parts: Vec<Part>,
+    pub(crate) input: Option<InputSource>,
+    pub(crate) format: Format,
+    pub(crate) timings: bool,
+    pub(crate) jobs: usize,
+    pub(crate) example: bool,
+    pub(crate) explain: bool,
+    pub(crate) cache: bool,
+    pub(crate) verbosity: u8,
+}
+
+const DEFAULT_ITERATIONS: usize = 10;
+const DEFAULT_ANSWERS: &str = "answers.toml";
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum CliError {
+    UnknownCommand(String),
+    UnknownOption(String),
+    UnsupportedOption { option: String, command: String },
+    MissingValue(String),
+    InvalidDay(String),
+    UnknownDay { day: u8, available: Vec<u8> },
+    InvalidPart(String),
+    InvalidFormat(String),
+    InvalidNumber { option: String, value: String },
+    Conflict(String, String),
+    SingleDayRequired(String),
+    MissingDay(String),
+    StdinNotSupported(String),
+    InvalidNewDay(String),
+    UnknownCacheCommand(Option<String>),
+    DayExists(u8),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownCommand(command) => write!(f, "unknown command '{}'", command),
+            CliError::UnknownOption(option) => write!(f, "unknown option '{}'", option),
+            CliError::UnsupportedOption { option, command } => write!(f, "option '{}' is not supported by '{}'", option, command),
+            CliError::MissingValue(option) => write!(f, "option '{}' requires a value", option),
+            CliError::InvalidDay(spec) => write!(f, "invalid day selection '{}', expected e.g. 5, 1-4 or 1,3,5-7", spec),
+            CliError::UnknownDay { day, available } => write!(f, "day {} is not available (available days: {})", day, format_days(available)),
+            CliError::InvalidPart(part) => write!(f, "invalid part '{}', expected 1 or 2", part),
+            CliError::InvalidFormat(format) => write!(f, "invalid format '{}', expected text, json or csv", format),
+            CliError::InvalidNumber { option, value } => write!(f, "invalid value '{}' for option '{}', expected a positive number", value, option),
+            CliError::Conflict(first, second) => write!(f, "options '{}' and '{}' cannot be combined", first, second),
+            CliError::SingleDayRequired(option) => write!(f, "option '{}' requires a single day selected with --day", option),
+            CliError::MissingDay(command) => write!(f, "command '{}' requires a single day", command),
+            CliError::StdinNotSupported(command) => write!(f, "command '{}' cannot read its input from stdin", command),
+            CliError::InvalidNewDay(day) => write!(f, "invalid day '{}', expected a number from 1 to 25", day),
+            CliError::DayExists(day) => write!(f, "day {} already exists", day),
+            CliError::UnknownCacheCommand(Some(command)) => write!(f, "unknown cache command '{}', expected 'cache clear'", command),
+            CliError::UnknownCacheCommand(None) => write!(f, "missing cache command, expected 'cache clear'"),
+        }
+    }
+}
+
+pub(crate) fn parse<I: IntoIterator<Item = String>>(args: I, available: &[u8]) -> Result<Command, CliError> {
+    let mut args = args.into_iter().peekable();
+
+    let command = match args.peek().map(String::as_str) {
+        Some("help") | Some("-h") | Some("--help") => return Ok(Command::Help),
+        Some("list") => return Ok(Command::List),
+        Some("cache") => {
+            args.next();
+            return match (args.next(), args.next()) {
+                (Some(command), None) if command == "clear" => Ok(Command::ClearCache),
+                (Some(command), Some(argument)) if command == "clear" => Err(CliError::UnknownOption(argument)),
+                (command, _) => Err(CliError::UnknownCacheCommand(command)),
+            };
+        }
+        Some("new-day") => {
+            args.next();
+            return parse_new_day(args, available);
+        }
+        Some(argument) if !argument.starts_with('-') => {
+            let command = argument.to_string();
+            args.next();
+            command
+        }
+        _ => "run".to_string(),
+    };
+    let supported: &[&str] = match command.as_str() {
+        "run" => &["--day", "--part", "--all", "--input", "--input-dir", "--format", "--time", "--jobs", "--example", "--explain", "--no-cache", "--verbose"],
+        "bench" => &["--day", "--part", "--all", "--input", "--input-dir", "--iterations"],
+        "verify" => &["--day", "--part", "--all", "--input", "--input-dir", "--answers", "--jobs", "--no-cache", "--verbose"],
+        "watch" => &["--day", "--part", "--input", "--input-dir", "--answers", "--interval", "--verbose"],
+        "repl" => &["--day", "--part"],
+        "generate" => &["--day", "--size", "--seed"],
+        _ => return Err(CliError::UnknownCommand(command)),
+    };
+
+    let mut days: Option<Vec<u8>> = None;
+    let mut all = false;
+    let mut parts = Part::ALL.to_vec();
+    let mut input: Option<(String, InputSource)> = None;
+    let mut format = Format::Text;
+    let mut timings = false;
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut answers = PathBuf::from(DEFAULT_ANSWERS);
+    let mut interval = DEFAULT_INTERVAL;
+    let mut jobs = 1;
+    let mut example = false;
+    let mut explain = false;
+    let mut cache = true;
+    let mut verbosity = 0u8;
+    let mut size = DEFAULT_SIZE;
+    let mut seed = DEFAULT_SEED;
+
+    while let Some(argument) = args.next() {
+        let (option, inline_value) = match argument.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => (option.to_string(), Some(value.to_string())),
+            _ => (argument, None),
+        };
+        let option = match option.as_str() {
+            "-d" => "--day".to_string(),
+            "-p" => "--part".to_string(),
+            "-a" => "--all".to_string(),
+            "-i" => "--input".to_string(),
+            "-f" => "--format".to_string(),
+            "-n" => "--iterations".to_string(),
+            "-j" => "--jobs".to_string(),
+            "-v" => "--verbose".to_string(),
+            "-vv" => {
+                verbosity = verbosity.saturating_add(1);
+                "--verbose".to_string()
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => option,
+        };
+        if !OPTIONS.contains(&option.as_str()) {
+            return Err(CliError::UnknownOption(option));
+        }
+        if !supported.contains(&option.as_str()) {
+            return Err(CliError::UnsupportedOption { option, command });
+        }
+        let mut value = || inline_value.clone().or_else(|| args.next()).ok_or_else(|| CliError::MissingValue(option.clone()));
+
+        match option.as_str() {
+            "--day" => {
+                let selected = parse_days(&value()?, available)?;
+                days.get_or_insert_with(Vec::new).extend(selected);
+            }
+            "--part" => parts = vec![parse_part(&value()?)?],
+            "--all" => all = true,
+            "--format" => format = parse_format(&value()?)?,
+            "--time" => timings = true,
+            "--example" => example = true,
+            "--explain" => explain = true,
+            "--no-cache" => cache = false,
+            "--verbose" => verbosity = verbosity.saturating_add(1),
+            "--seed" => {
+                let value = value()?;
+                seed = value.parse().map_err(|_| CliError::InvalidNumber { option: option.clone(), value })?;
+            }
+            "--iterations" | "--interval" | "--jobs" | "--size" => {
+                let value = value()?;
+                let number = value.parse::<usize>().ok().filter(|number| *number > 0)
+                    .ok_or_else(|| CliError::InvalidNumber { option: option.clone(), value })?;
+                match option.as_str() {
+                    "--iterations" => iterations = number,
+                    "--interval" => interval = Duration::from_millis(number as u64),
+                    "--size" => size = number,
+                    _ => jobs = number,
+                }
+            }
+            "--answers" => answers = PathBuf::from(value()?),
+            _ => {
+                let value = value()?;
+                let source = if option == "--input-dir" {
+                    InputSource::Directory(PathBuf::from(value))
+                } else if value == "-" {
+                    InputSource::Stdin
+                } else {
+                    InputSource::File(PathBuf::from(value))
+                };
+                if let Some((previous, _)) = input.replace((option.clone(), source)) {
+                    return Err(CliError::Conflict(previous, option));
+                }
+            }
+        }
+    }
+
+    let days = match (days, all) {
+        (Some(_), true) => return Err(CliError::Conflict("--all".to_string(), "--day".to_string())),
+        (Some(mut days), false) => {
+            days.sort();
+            days.dedup();
+            days
+        }
+        (None, _) => available.to_vec(),
+    };
+
+    if let (Some((option, _)), true) = (&input, example) {
+        return Err(CliError::Conflict(option.clone(), "--example".to_string()));
+    }
+    if explain && format != Format::Text {
+        return Err(CliError::Conflict("--format".to_string(), "--explain".to_string()));
+    }
+    if let Some((option, InputSource::File(_) | InputSource::Stdin)) = &input {
+        if days.len() != 1 {
+            return Err(CliError::SingleDayRequired(option.clone()));
+        }
+    }
+
+    if matches!(command.as_str(), "watch" | "repl" | "generate") && days.len() != 1 {
+        return Err(CliError::MissingDay(command));
+    }
+    // watch reads the input again on every change, stdin can only be read once
+    if command == "watch" && matches!(input, Some((_, InputSource::Stdin))) {
+        return Err(CliError::StdinNotSupported(command));
+    }
+
+    let options = RunOptions { days, parts, input: input.map(|(_, source)| source), format, timings, jobs, example, explain, cache, verbosity };
+    Ok(match command.as_str() {
+        "bench" => Command::Bench(options, iterations),
+        "verify" => Command::Verify(options, answers),
+        "watch" => Command::Watch(options, answers, interval),
+        "repl" => Command::Repl(options),
+        "generate" => Command::Generate(options, size, seed),
+        _ => Command::Run(options),
+    })
+}
+
+fn parse_new_day<I: Iterator<Item = String>>(mut args: I, available: &[u8]) -> Result<Command, CliError> {
+    let value = args.next().ok_or_else(|| CliError::MissingDay("new-day".to_string()))?;
+    if let Some(argument) = args.next() {
+        return Err(CliError::UnknownOption(argument));
+    }
+    let day = value.parse::<u8>().ok().filter(|day| (1..=25).contains(day)).ok_or(CliError::InvalidNewDay(value))?;
+    if available.contains(&day) {
+        return Err(CliError::DayExists(day));
+    }
+    Ok(Command::NewDay(day))
+}
+
+const OPTIONS: [&str; 17] = [
+    "--day", "--part", "--all", "--input", "--input-dir", "--format", "--time", "--iterations", "--answers", "--interval", "--jobs", "--example", "--no-cache",
+    "--verbose", "--explain", "--size", "--seed",
+];
+
+fn parse_days(spec: &str, available: &[u8]) -> Result<Vec<u8>, CliError> {
+    let invalid = || CliError::InvalidDay(spec.to_string());
+    let parse_day = |day: &str| day.trim().parse::<u8>().map_err(|_| invalid());
+
+    let mut days = Vec::new();
+    for element in spec.split(',') {
+        let (start, end) = match element.split_once('-') {
+            Some((start, end)) => (parse_day(start)?, parse_day(end)?),
+            None => {
+                let day = parse_day(element)?;
+                (day, day)
+            }
+        };
+        if start > end {
+            return Err(invalid());
+        }
+        for day in start..=end {
+            if !available.contains(&day) {
+                return Err(CliError::UnknownDay { day, available: available.to_vec() });
+            }
+            days.push(day);
+        }
+    }
+    Ok(days)
+}
+
+fn parse_part(value: &str) -> Result<Part, CliError> {
+    Part::parse(value.trim()).ok_or_else(|| CliError::InvalidPart(value.to_string()))
+}
+
+fn parse_format(value: &str) -> Result<Format, CliError> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(CliError::InvalidFormat(value.to_string())),
+    }
+}
+
+fn format_days(days: &[u8]) -> String {
+    let mut groups = Vec::<String>::new();
+    let mut iter = days.iter().peekable();
+    while let Some(&start) = iter.next() {
+        let mut end = start;
+        while iter.peek().is_some_and(|&&next| next == end + 1) {
+            end += 1;
+            iter.next();
+        }
+        groups.push(if start == end { start.to_string() } else { format!("{}-{}", start, end) });
+    }
+    groups.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AVAILABLE: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+
+    fn parse_args(args: &[&str]) -> Result<Command, CliError> {
+        parse(args.iter().map(|arg| arg.to_string()), &AVAILABLE)
+    }
+
+    fn run(days: &[u8], parts: &[Part]) -> Result<Command, CliError> {
+        run_with_input(days, parts, None)
+    }
+
+    fn run_with_input(days: &[u8], parts: &[Part], input: Option<InputSource>) -> Result<Command, CliError> {
+        Ok(Command::Run(RunOptions { days: days.to_vec(), parts: parts.to_vec(), input, format: Format::Text, timings: false, jobs: 1, example: false, explain: false, cache: true, verbosity: 0 }))
+    }
+
+    #[test]
+    fn test_defaults_to_all_days() {
+        assert_eq!(parse_args(&[]), run(&AVAILABLE, &Part::ALL));
+        assert_eq!(parse_args(&["run"]), run(&AVAILABLE, &Part::ALL));
+        assert_eq!(parse_args(&["run", "--all"]), run(&AVAILABLE, &Part::ALL));
+        assert_eq!(parse_args(&["list"]), Ok(Command::List));
+    }
+
+    #[test]
+    fn test_day_and_part() {
+        assert_eq!(parse_args(&["run", "--day", "5", "--part", "2"]), run(&[5], &[Part::Two]));
+        assert_eq!(parse_args(&["--day=5", "--part=1"]), run(&[5], &[Part::One]));
+    }
+
+    #[test]
+    fn test_day_ranges() {
+        assert_eq!(parse_args(&["run", "--day", "1-4"]), run(&[1, 2, 3, 4], &Part::ALL));
+        assert_eq!(parse_args(&["run", "--day", "6,1-2", "--day", "2"]), run(&[1, 2, 6], &Part::ALL));
+    }
+
+    #[test]
+    fn test_input() {
+        assert_eq!(parse_args(&["run", "--day", "5", "--input", "almanac.txt"]), run_with_input(&[5], &Part::ALL, Some(InputSource::File(PathBuf::from("almanac.txt")))));
+        assert_eq!(parse_args(&["run", "--day", "5", "--input", "-"]), run_with_input(&[5], &Part::ALL, Some(InputSource::Stdin)));
+        assert_eq!(parse_args(&["run", "--input-dir", "inputs"]), run_with_input(&AVAILABLE, &Part::ALL, Some(InputSource::Directory(PathBuf::from("inputs")))));
+        assert_eq!(parse_args(&["run", "--day", "1-2", "--input", "-"]), Err(CliError::SingleDayRequired("--input".to_string())));
+        assert_eq!(parse_args(&["run", "--input", "a", "--input-dir", "b"]), Err(CliError::Conflict("--input".to_string(), "--input-dir".to_string())));
+    }
+
+    #[test]
+    fn test_example() {
+        let Ok(Command::Run(options)) = parse_args(&["run", "--day", "5", "--example"]) else { panic!("expected run") };
+        assert!(options.example);
+        assert_eq!(parse_args(&["run", "--input-dir", "inputs", "--example"]), Err(CliError::Conflict("--input-dir".to_string(), "--example".to_string())));
+        assert_eq!(parse_args(&["verify", "--example"]), Err(CliError::UnsupportedOption { option: "--example".to_string(), command: "verify".to_string() }));
+    }
+
+    #[test]
+    fn test_format() {
+        let Ok(Command::Run(options)) = parse_args(&["run", "--format", "json"]) else { panic!("expected run") };
+        assert_eq!(options.format, Format::Json);
+        let Ok(Command::Run(options)) = parse_args(&["--format=csv"]) else { panic!("expected run") };
+        assert_eq!(options.format, Format::Csv);
+        assert_eq!(parse_args(&["--format", "xml"]), Err(CliError::InvalidFormat("xml".to_string())));
+    }
+
+    #[test]
+    fn test_bench() {
+        let Ok(Command::Bench(options, iterations)) = parse_args(&["bench", "--day", "5", "--iterations", "3"]) else { panic!("expected bench") };
+        assert_eq!((options.days, iterations), (vec![5], 3));
+        let Ok(Command::Bench(_, iterations)) = parse_args(&["bench"]) else { panic!("expected bench") };
+        assert_eq!(iterations, DEFAULT_ITERATIONS);
+        let Ok(Command::Run(options)) = parse_args(&["run", "--time"]) else { panic!("expected run") };
+        assert!(options.timings);
+
+        assert_eq!(parse_args(&["bench", "-n", "0"]), Err(CliError::InvalidNumber { option: "--iterations".to_string(), value: "0".to_string() }));
+        assert_eq!(parse_args(&["bench", "--format", "json"]), Err(CliError::UnsupportedOption { option: "--format".to_string(), command: "bench".to_string() }));
+        assert_eq!(parse_args(&["run", "--iterations", "2"]), Err(CliError::UnsupportedOption { option: "--iterations".to_string(), command: "run".to_string() }));
+    }
+
+    #[test]
+    fn test_jobs() {
+        let Ok(Command::Run(options)) = parse_args(&["run", "-j", "4"]) else { panic!("expected run") };
+        assert_eq!(options.jobs, 4);
+        let Ok(Command::Verify(options, _)) = parse_args(&["verify", "--jobs=2"]) else { panic!("expected verify") };
+        assert_eq!(options.jobs, 2);
+        let Ok(Command::Run(options)) = parse_args(&["run"]) else { panic!("expected run") };
+        assert_eq!(options.jobs, 1);
+
+        assert_eq!(parse_args(&["run", "--jobs", "0"]), Err(CliError::InvalidNumber { option: "--jobs".to_string(), value: "0".to_string() }));
+        assert_eq!(parse_args(&["bench", "-j", "2"]), Err(CliError::UnsupportedOption { option: "--jobs".to_string(), command: "bench".to_string() }));
+    }
+
+    #[test]
+    fn test_explain() {
+        let Ok(Command::Run(options)) = parse_args(&["run", "--explain", "--example"]) else { panic!("expected run") };
+        assert!(options.explain && options.example);
+        let Ok(Command::Run(options)) = parse_args(&["run"]) else { panic!("expected run") };
+        assert!(!options.explain);
+
+        assert_eq!(parse_args(&["run", "--explain", "--format", "json"]), Err(CliError::Conflict("--format".to_string(), "--explain".to_string())));
+        assert_eq!(parse_args(&["verify", "--explain"]), Err(CliError::UnsupportedOption { option: "--explain".to_string(), command: "verify".to_string() }));
+    }
+
+    #[test]
+    fn test_verbose() {
+        let Ok(Command::Verify(options, _)) = parse_args(&["verify", "-v", "--verbose"]) else { panic!("expected verify") };
+        assert_eq!(options.verbosity, 2);
+        let Ok(Command::Run(options)) = parse_args(&["-vv"]) else { panic!("expected run") };
+        assert_eq!(options.verbosity, 2);
+        let Ok(Command::Run(options)) = parse_args(&["run"]) else { panic!("expected run") };
+        assert_eq!(options.verbosity, 0);
+
+        assert_eq!(parse_args(&["bench", "-v"]), Err(CliError::UnsupportedOption { option: "--verbose".to_string(), command: "bench".to_string() }));
+    }
+
+    #[test]
+    fn test_verify() {
+        let Ok(Command::Verify(options, answers)) = parse_args(&["verify", "--day", "1-2"]) else { panic!("expected verify") };
+        assert_eq!((options.days, answers), (vec![1, 2], PathBuf::from(DEFAULT_ANSWERS)));
+        let Ok(Command::Verify(_, answers)) = parse_args(&["verify", "--answers=expected.toml"]) else { panic!("expected verify") };
+        assert_eq!(answers, PathBuf::from("expected.toml"));
+
+        assert_eq!(parse_args(&["run", "--answers", "a.toml"]), Err(CliError::UnsupportedOption { option: "--answers".to_string(), command: "run".to_string() }));
+    }
+
+    #[test]
+    fn test_watch() {
+        let Ok(Command::Watch(options, _, interval)) = parse_args(&["watch", "--day", "5", "--interval", "100"]) else { panic!("expected watch") };
+        assert_eq!((options.days, interval), (vec![5], Duration::from_millis(100)));
+        let Ok(Command::Watch(_, _, interval)) = parse_args(&["watch", "-d", "5"]) else { panic!("expected watch") };
+        assert_eq!(interval, DEFAULT_INTERVAL);
+
+        assert_eq!(parse_args(&["watch"]), Err(CliError::MissingDay("watch".to_string())));
+        assert_eq!(parse_args(&["watch", "--day", "1-2"]), Err(CliError::MissingDay("watch".to_string())));
+        assert_eq!(parse_args(&["watch", "--day", "5", "--input", "-"]), Err(CliError::StdinNotSupported("watch".to_string())));
+    }
+
+    #[test]
+    fn test_generate() {
+        let Ok(Command::Generate(options, size, seed)) = parse_args(&["generate", "-d", "3", "--size", "20", "--seed=0"]) else { panic!("expected generate") };
+        assert_eq!((options.days, size, seed), (vec![3], 20, 0));
+        let Ok(Command::Generate(_, size, seed)) = parse_args(&["generate", "-d", "3"]) else { panic!("expected generate") };
+        assert_eq!((size, seed), (DEFAULT_SIZE, DEFAULT_SEED));
+
+        assert_eq!(parse_args(&["generate", "-d", "3", "--seed", "x"]), Err(CliError::InvalidNumber { option: "--seed".to_string(), value: "x".to_string() }));
+        assert_eq!(parse_args(&["generate", "--size", "5"]), Err(CliError::MissingDay("generate".to_string())));
+    }
+
+    #[test]
+    fn test_repl() {
+        let Ok(Command::Repl(options)) = parse_args(&["repl", "--day", "7", "-p", "2"]) else { panic!("expected repl") };
+        assert_eq!((options.days, options.parts), (vec![7], vec![Part::Two]));
+
+        assert_eq!(parse_args(&["repl"]), Err(CliError::MissingDay("repl".to_string())));
+        assert_eq!(parse_args(&["repl", "-d", "7", "--input", "-"]), Err(CliError::UnsupportedOption { option: "--input".to_string(), command: "repl".to_string() }));
+    }
+
+    #[test]
+    fn test_cache() {
+        let Ok(Command::Run(options)) = parse_args(&["run", "--no-cache"]) else { panic!("expected run") };
+        assert!(!options.cache);
+        assert_eq!(parse_args(&["cache", "clear"]), Ok(Command::ClearCache));
+        assert_eq!(parse_args(&["cache"]), Err(CliError::UnknownCacheCommand(None)));
+        assert_eq!(parse_args(&["cache", "drop"]), Err(CliError::UnknownCacheCommand(Some("drop".to_string()))));
+        assert_eq!(parse_args(&["bench", "--no-cache"]), Err(CliError::UnsupportedOption { option: "--no-cache".to_string(), command: "bench".to_string() }));
+    }
+
+    #[test]
+    fn test_new_day() {
+        assert_eq!(parse_args(&["new-day", "8"]), Ok(Command::NewDay(8)));
+        assert_eq!(parse_args(&["new-day"]), Err(CliError::MissingDay("new-day".to_string())));
+        assert_eq!(parse_args(&["new-day", "26"]), Err(CliError::InvalidNewDay("26".to_string())));
+        assert_eq!(parse_args(&["new-day", "7"]), Err(CliError::DayExists(7)));
+        assert_eq!(parse_args(&["new-day", "8", "9"]), Err(CliError::UnknownOption("9".to_string())));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_args(&["run", "--day", "9"]), Err(CliError::UnknownDay { day: 9, available: AVAILABLE.to_vec() }));
+        assert_eq!(parse_args(&["run", "--day", "4-1"]), Err(CliError::InvalidDay("4-1".to_string())));
+        assert_eq!(parse_args(&["run", "--day", "x"]), Err(CliError::InvalidDay("x".to_string())));
+        assert_eq!(parse_args(&["run", "--part", "3"]), Err(CliError::InvalidPart("3".to_string())));
+        assert_eq!(parse_args(&["run", "--day"]), Err(CliError::MissingValue("--day".to_string())));
+        assert_eq!(parse_args(&["run", "--all", "--day", "1"]), Err(CliError::Conflict("--all".to_string(), "--day".to_string())));
+        assert_eq!(parse_args(&["walk"]), Err(CliError::UnknownCommand("walk".to_string())));
+        assert_eq!(parse_args(&["--days"]), Err(CliError::UnknownOption("--days".to_string())));
+    }
+
+    #[test]
+    fn test_format_days() {
+        assert_eq!(format_days(&[1, 2, 3, 5, 7, 8]), "1-3,5,7-8");
+        assert_eq!(CliError::UnknownDay { day: 9, available: AVAILABLE.to_vec() }.to_string(), "day 9 is not available (available days: 1-7)");
+    }
+}
@@ -0,0 +1,132 @@
+use std::fmt;
+
+/// A malformed input line, `line` and `column` are 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // `text` should be a slice of `line`, its position determines the reported column
+    pub fn new(day: u8, line: &str, text: &str, message: &str) -> ParseError {
+        ParseError {
+            day,
+            line: 0,
+            column: column_of(line, text),
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+
+    // moves a column relative to `text` to a column relative to `line`, when `text` is a slice of `line`
+    pub fn within(mut self, line: &str, text: &str) -> ParseError {
+        self.column += column_of(line, text) - 1;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {}, line {}, column {}: {} '{}'", self.day, self.line, self.column, self.message, self.text)
+    }
+}
+
+/// Why a part could not be solved: its input is malformed, or its answer does not fit into an `Answer`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    Parse(ParseError),
+    Overflow { day: u8, message: String },
+}
+
+impl SolveError {
+    pub fn overflow(day: u8, message: &str) -> SolveError {
+        SolveError::Overflow { day, message: message.to_string() }
+    }
+
+    // only parse errors point at a line
+    pub fn at_line(self, line: usize) -> SolveError {
+        match self {
+            SolveError::Parse(error) => SolveError::Parse(error.at_line(line)),
+            overflow => overflow,
+        }
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> SolveError {
+        SolveError::Parse(error)
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Parse(error) => error.fmt(f),
+            SolveError::Overflow { day, message } => write!(f, "day {}: {}", day, message),
+        }
+    }
+}
+
+fn column_of(line: &str, text: &str) -> usize {
+    let offset = (text.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset.checked_add(text.len()).is_some_and(|end| end <= line.len()) && line.is_char_boundary(offset) {
+        line[..offset].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_of_slice() {
+        let line = "Game 1: x blue";
+        let error = ParseError::new(2, line, &line[8..9], "invalid number").at_line(3);
+
+        assert_eq!(error.column, 9);
+        assert_eq!(error.to_string(), "day 2, line 3, column 9: invalid number 'x'");
+    }
+
+    #[test]
+    fn test_column_counts_characters() {
+        let line = "äöü 12x";
+        let error = ParseError::new(1, line, &line[7..], "invalid number");
+
+        assert_eq!(error.column, 5);
+    }
+
+    #[test]
+    fn test_column_of_unrelated_text() {
+        assert_eq!(ParseError::new(1, "line", "other", "invalid").column, 1);
+    }
+
+    #[test]
+    fn test_solve_error() {
+        let line = "Time: x";
+        let error = SolveError::from(ParseError::new(6, line, &line[6..], "invalid number")).at_line(1);
+        assert_eq!(error.to_string(), "day 6, line 1, column 7: invalid number 'x'");
+
+        let error = SolveError::overflow(6, "the product of the winning counts overflows").at_line(1);
+        assert_eq!(error.to_string(), "day 6: the product of the winning counts overflows");
+    }
+
+    #[test]
+    fn test_within() {
+        let line = "  32T3X 765";
+        let hand = &line[2..7];
+        let error = ParseError::new(7, hand, &hand[4..5], "invalid card").within(line, hand);
+
+        assert_eq!(error.column, 7);
+    }
+}
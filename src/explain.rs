@@ -0,0 +1,113 @@
+use std::io;
+use std::io::Write;
+
+use crate::input::InputSource;
+use crate::solution::{Answer, Part, Prepared, Puzzle};
+
+/// Writes the answer of every part followed by the items it adds up from, one per line.
+///
+/// Days whose answer is not a sum of items, like a minimum or a product, only get their answer.
+pub fn write_explanation<W: Write>(out: &mut W, label: &str, prepared: &dyn Prepared, parts: &[Part]) -> io::Result<bool> {
+    let mut success = true;
+    for part in parts {
+        let answer = match prepared.solve(*part) {
+            Ok(answer) => answer,
+            Err(error) => {
+                writeln!(out, "{} part {}: error: {}", label, part, error)?;
+                success = false;
+                continue;
+            }
+        };
+        writeln!(out, "{} part {}: {}", label, part, answer)?;
+        let contributions = prepared.explain(*part);
+        if contributions.is_empty() {
+            writeln!(out, "  no per-item explanation")?;
+            continue;
+        }
+        let width = contributions.iter().map(|contribution| contribution.item.chars().count()).max().unwrap_or(0);
+        for contribution in &contributions {
+            writeln!(out, "  {:<width$}  {}", contribution.item, contribution.value, width = width)?;
+        }
+        let total = contributions.iter().map(|contribution| contribution.value).sum::<Answer>();
+        if total != answer {
+            writeln!(out, "  the items add up to {}, not the answer", total)?;
+            success = false;
+        }
+    }
+    Ok(success)
+}
+
+// explains the real input, or every example with the parts it has an expected answer for
+pub fn explain_all<W: Write>(out: &mut W, puzzles: &[&dyn Puzzle], parts: &[Part], source: &InputSource, example: bool) -> io::Result<bool> {
+    let mut success = true;
+    for puzzle in puzzles {
+        let day = puzzle.day();
+        let inputs = if example {
+            puzzle.examples().iter().enumerate().map(|(index, example)| {
+                let parts = parts.iter().copied().filter(|part| example.expected(*part).is_some()).collect();
+                (format!("day {} example {}", day, index + 1), Ok(example.input.to_string()), parts)
+            }).collect()
+        } else {
+            vec![(format!("day {}", day), source.read(day).map_err(|error| error.to_string()), parts.to_vec())]
+        };
+        for (label, input, parts) in inputs {
+            match input.and_then(|input| puzzle.prepare(&input).map_err(|error| error.to_string())) {
+                Ok(prepared) => success &= write_explanation(out, &label, prepared.as_ref(), &parts)?,
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    success = false;
+                }
+            }
+        }
+    }
+    Ok(success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle02::Day02;
+    use crate::puzzle06::{Day06, EXAMPLES};
+    use crate::registry::PUZZLES;
+    use crate::solution::Puzzle;
+
+    #[test]
+    fn test_write_explanation() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green\nGame 2: 20 red";
+        let prepared = Day02.prepare(input).unwrap();
+        let mut out = Vec::new();
+
+        assert!(write_explanation(&mut out, "day 2", prepared.as_ref(), &Part::ALL).unwrap());
+        assert_eq!(String::from_utf8(out).unwrap(), "day 2 part 1: 1\n\
+                                                     \x20 game 1: possible    1\n\
+                                                     \x20 game 2: impossible  0\n\
+                                                     day 2 part 2: 24\n\
+                                                     \x20 game 1: 4 red, 2 green, 3 blue   24\n\
+                                                     \x20 game 2: 20 red, 0 green, 0 blue  0\n");
+    }
+
+    #[test]
+    fn test_days_without_explanation() {
+        let prepared = Day06.prepare(EXAMPLES[0].input).unwrap();
+        let mut out = Vec::new();
+
+        assert!(write_explanation(&mut out, "day 6", prepared.as_ref(), &[Part::One]).unwrap());
+        assert_eq!(String::from_utf8(out).unwrap(), "day 6 part 1: 288\n  no per-item explanation\n");
+    }
+
+    #[test]
+    fn test_contributions_add_up_to_the_answer() {
+        for puzzle in PUZZLES {
+            for example in puzzle.examples() {
+                let prepared = puzzle.prepare(example.input).unwrap();
+                for part in Part::ALL.into_iter().filter(|part| example.expected(*part).is_some()) {
+                    let contributions = prepared.explain(part);
+                    if !contributions.is_empty() {
+                        let total = contributions.iter().map(|contribution| contribution.value).sum::<Answer>();
+                        assert_eq!(Some(total), example.expected(part), "day {} part {}", puzzle.day(), part);
+                    }
+                }
+            }
+        }
+    }
+}
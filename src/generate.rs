@@ -0,0 +1,92 @@
+use std::ops::RangeInclusive;
+
+use crate::solution::Puzzle;
+
+pub const DEFAULT_SEED: u64 = 2023;
+pub const DEFAULT_SIZE: usize = 100;
+
+/// A small seeded pseudo random number generator (SplitMix64), the same seed always gives the same input.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // a number below `bound`, the slight modulo bias does not matter for test inputs
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "empty range");
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
+
+// an input of `puzzle` with `size` items (lines, games, cards, ...), `None` if the day has no generator
+pub fn generate(puzzle: &dyn Puzzle, size: usize, seed: u64) -> Option<String> {
+    puzzle.generate(&mut Rng::new(seed), size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::PUZZLES;
+    use crate::solution::Part;
+
+    #[test]
+    fn test_rng_is_seeded() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.below(1000)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(DEFAULT_SEED);
+        let values = (0..1000).map(|_| rng.range(3..=5)).collect::<Vec<_>>();
+
+        assert!(values.iter().all(|value| (3..=5).contains(value)));
+        assert!([3, 4, 5].iter().all(|value| values.contains(value)));
+    }
+
+    #[test]
+    fn test_generated_inputs_are_solved() {
+        for puzzle in PUZZLES {
+            for seed in 0..20 {
+                let input = generate(*puzzle, 1 + seed as usize % 8, seed).expect("every day has a generator");
+                let prepared = puzzle.prepare(&input).unwrap_or_else(|error| panic!("day {} seed {}: {}", puzzle.day(), seed, error));
+                for part in Part::ALL {
+                    assert!(prepared.solve(part).is_ok(), "day {} seed {} part {}", puzzle.day(), seed, part);
+                }
+            }
+            assert_eq!(generate(*puzzle, 10, 7), generate(*puzzle, 10, 7));
+        }
+    }
+}
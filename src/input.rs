@@ -0,0 +1,114 @@
+use std::fmt;
+use std::fs::read_to_string;
+use std::io;
+use std::io::Read;
+use std::path::PathBuf;
+
+pub const INPUT_DIR_VARIABLE: &str = "AOC_INPUT_DIR";
+
+/// Where the puzzle inputs are read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Directory(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { day: u8, path: PathBuf },
+    Unreadable { day: u8, name: String, error: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { day, path } => write!(
+                f, "day {}: input file '{}' not found (use --input, --input-dir or {} to point to the puzzle input)",
+                day, path.display(), INPUT_DIR_VARIABLE),
+            InputError::Unreadable { day, name, error } => write!(f, "day {}: cannot read input '{}': {}", day, name, error),
+        }
+    }
+}
+
+impl InputSource {
+    // an explicit source wins over the environment, which wins over the current directory
+    pub fn resolve(explicit: Option<InputSource>) -> InputSource {
+        explicit.unwrap_or_else(|| {
+            let directory = std::env::var_os(INPUT_DIR_VARIABLE).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+            InputSource::Directory(directory)
+        })
+    }
+
+    // the file holding the input of `day`, `None` for stdin
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Stdin => None,
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Directory(directory) => Some(directory.join(data_file_name(day))),
+        }
+    }
+
+    pub fn name(&self, day: u8) -> String {
+        self.path(day).map(|path| path.display().to_string()).unwrap_or_else(|| "-".to_string())
+    }
+
+    pub fn read(&self, day: u8) -> Result<String, InputError> {
+        let Some(path) = self.path(day) else {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)
+                .map_err(|error| InputError::Unreadable { day, name: self.name(day), error })?;
+            return Ok(content);
+        };
+
+        match read_to_string(&path) {
+            Ok(content) => Ok(content),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Err(InputError::Missing { day, path }),
+            Err(error) => Err(InputError::Unreadable { day, name: path.display().to_string(), error }),
+        }
+    }
+}
+
+pub fn data_file_name(day: u8) -> String {
+    format!("{:02}_data.txt", day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use crate::testing::temp_directory;
+
+    #[test]
+    fn test_read_from_directory() {
+        let directory = temp_directory("input-directory");
+        fs::write(directory.join("05_data.txt"), "seeds: 1 2").unwrap();
+
+        let source = InputSource::Directory(directory.clone());
+        let input = source.read(5).unwrap();
+
+        assert_eq!(input, "seeds: 1 2");
+        assert_eq!(source.name(5), directory.join("05_data.txt").display().to_string());
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file_is_named() {
+        let directory = temp_directory("input-missing");
+
+        let error = InputSource::Directory(directory.clone()).read(3).unwrap_err();
+
+        assert!(matches!(error, InputError::Missing { day: 3, .. }));
+        assert!(error.to_string().starts_with(&format!("day 3: input file '{}' not found", directory.join("03_data.txt").display())));
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_explicit_source_wins() {
+        let source = InputSource::resolve(Some(InputSource::File(PathBuf::from("input.txt"))));
+
+        assert_eq!(source, InputSource::File(PathBuf::from("input.txt")));
+        assert_eq!(source.name(1), "input.txt");
+        assert_eq!(InputSource::Stdin.name(1), "-");
+    }
+}
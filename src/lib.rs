@@ -0,0 +1,31 @@
+//! Solutions for Advent of Code 2023.
+//!
+//! Every day lives in its own `puzzleNN` module exposing its parser and solvers, and implements
+//! [`solution::Solution`]. The [`registry`] lists all days, the [`runner`] reads their input and solves them,
+//! the [`report`] writes the answers as text, json or csv
+//! and [`verify`] compares them with the expected answers.
+
+pub mod bench;
+pub mod cache;
+pub mod error;
+pub mod explain;
+pub mod generate;
+pub mod input;
+pub mod puzzle01;
+pub mod puzzle02;
+pub mod puzzle03;
+pub mod puzzle04;
+pub mod puzzle05;
+pub mod puzzle06;
+pub mod puzzle07;
+pub mod registry;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+#[cfg(test)]
+mod testing;
+pub mod trace;
+pub mod verify;
+pub mod watch;
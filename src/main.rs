@@ -1,17 +1,179 @@
-mod puzzle01;
-mod puzzle02;
-mod puzzle03;
-mod puzzle04;
-mod puzzle05;
-mod puzzle06;
-mod puzzle07;
-
-fn main() {
-    puzzle01::run();
-    puzzle02::run();
-    puzzle03::run();
-    puzzle04::run();
-    puzzle05::run();
-    puzzle06::run();
-    puzzle07::run();
+use std::io;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc_2023::cache::{Cache, CACHE_FILE};
+use aoc_2023::input::InputSource;
+use aoc_2023::report::{self, Reporter};
+use aoc_2023::runner::Record;
+use aoc_2023::solution::{Part, Puzzle};
+use aoc_2023::verify::{Answers, AnswersError};
+use aoc_2023::{bench, cache, explain, generate, registry, repl, runner, scaffold, trace, verify, watch};
+
+use crate::cli::Command;
+
+mod cli;
+
+fn main() -> ExitCode {
+    let command = match cli::parse(std::env::args().skip(1), &registry::days()) {
+        Ok(command) => command,
+        Err(error) => return usage_error(error),
+    };
+
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::List => {
+            for puzzle in registry::PUZZLES {
+                println!("day {}: {}", puzzle.day(), puzzle.title());
+            }
+        }
+        Command::Run(options) => {
+            trace::set_verbosity(options.verbosity);
+            let cached = cached(&options);
+            let source = InputSource::resolve(options.input);
+            let reporter = || Reporter::new(options.format, options.timings, io::stdout().lock());
+            let result = if options.explain {
+                explain::explain_all(&mut io::stdout().lock(), &selected(&options.days), &options.parts, &source, options.example)
+            } else if options.example {
+                reporter().and_then(|reporter| report::write_all(reporter, runner::run_examples(&selected(&options.days), &options.parts)))
+            } else {
+                reporter().and_then(|reporter| report::write_all(reporter, records(&options.days, &options.parts, &source, options.jobs, cached)))
+            };
+            match result {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(error) => {
+                    eprintln!("error: cannot write output: {}", error);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::ClearCache => {
+            if let Err(error) = Cache::clear(Path::new(CACHE_FILE)) {
+                eprintln!("error: {}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::NewDay(day) => match scaffold::scaffold(Path::new("."), day) {
+            Ok(paths) => {
+                for path in paths {
+                    println!("wrote {}", path.display());
+                }
+            }
+            Err(error) => {
+                eprintln!("error: {} (run new-day from the repository root)", error);
+                return ExitCode::FAILURE;
+            }
+        },
+        Command::Verify(options, answers) => {
+            let answers = match Answers::load(&answers) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    return ExitCode::FAILURE;
+                }
+            };
+            trace::set_verbosity(options.verbosity);
+            let cached = cached(&options);
+            let source = InputSource::resolve(options.input);
+            let records = records(&options.days, &options.parts, &source, options.jobs, cached);
+            match verify::write_verification(&mut io::stdout().lock(), &records, &answers) {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(error) => {
+                    eprintln!("error: cannot write output: {}", error);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Watch(options, answers, interval) => {
+            // without an answers file the answers are shown without verification
+            let answers = match Answers::load(&answers) {
+                Ok(answers) => Some(answers),
+                Err(AnswersError::Io { error, .. }) if error.kind() == io::ErrorKind::NotFound => None,
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    return ExitCode::FAILURE;
+                }
+            };
+            trace::set_verbosity(options.verbosity);
+            let source = InputSource::resolve(options.input);
+            let puzzle = match single_puzzle(&options.days, "watch") {
+                Ok(puzzle) => puzzle,
+                Err(error) => return usage_error(error),
+            };
+            if let Err(error) = watch::watch(&mut io::stdout().lock(), puzzle, &options.parts, &source, answers.as_ref(), interval) {
+                eprintln!("error: cannot write output: {}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Repl(options) => {
+            let puzzle = match single_puzzle(&options.days, "repl") {
+                Ok(puzzle) => puzzle,
+                Err(error) => return usage_error(error),
+            };
+            let stdin = io::stdin();
+            let prompt = if stdin.is_terminal() { format!("day {}> ", puzzle.day()) } else { String::new() };
+            if let Err(error) = repl::repl(puzzle, &options.parts, stdin.lock(), &mut io::stdout().lock(), &prompt) {
+                eprintln!("error: {}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Generate(options, size, seed) => {
+            let puzzle = match single_puzzle(&options.days, "generate") {
+                Ok(puzzle) => puzzle,
+                Err(error) => return usage_error(error),
+            };
+            match generate::generate(puzzle, size, seed) {
+                Some(input) => print!("{}", input),
+                None => {
+                    eprintln!("error: day {} has no input generator", puzzle.day());
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Bench(options, iterations) => {
+            let source = InputSource::resolve(options.input);
+            match bench::bench_all(&mut io::stdout().lock(), &selected(&options.days), &options.parts, &source, iterations) {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(error) => {
+                    eprintln!("error: cannot write output: {}", error);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn usage_error(error: cli::CliError) -> ExitCode {
+    eprintln!("error: {}\n\n{}", error, cli::USAGE);
+    ExitCode::from(2)
+}
+
+// the day of a command that runs a single one
+fn single_puzzle(days: &[u8], command: &str) -> Result<&'static dyn Puzzle, cli::CliError> {
+    match *days {
+        [day] => registry::find(day).ok_or_else(|| cli::CliError::UnknownDay { day, available: registry::days() }),
+        _ => Err(cli::CliError::MissingDay(command.to_string())),
+    }
+}
+
+// cached answers have no solver events to show, so verbose runs always solve
+fn cached(options: &cli::RunOptions) -> bool {
+    options.cache && options.verbosity == 0
+}
+
+fn selected(days: &[u8]) -> Vec<&'static dyn Puzzle> {
+    registry::PUZZLES.iter().filter(|puzzle| days.contains(&puzzle.day())).copied().collect()
+}
+
+fn records(days: &[u8], parts: &[Part], source: &InputSource, jobs: usize, cached: bool) -> Vec<Record> {
+    if cached {
+        cache::run_all_cached(&selected(days), parts, source, jobs)
+    } else {
+        runner::run_all(&selected(days), parts, source, jobs)
+    }
 }
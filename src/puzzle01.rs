@@ -1,82 +1,400 @@
+use std::borrow::Cow;
+use std::fmt;
 use std::fs::read_to_string;
+use std::io;
+use std::io::BufRead;
+use std::path::Path;
+use std::sync::OnceLock;
 
-pub(crate) fn puzzle_01(part: i32) {
-    let string = read_to_string("./01_data.txt").unwrap();
-    let lines = string.lines().collect::<Vec<_>>();
-    let sum = sum_lines(lines, part);
-    println!("day 1 part {}: {}", part, sum)
+use crate::error::{ParseError, SolveError};
+use crate::event;
+use crate::generate::Rng;
+use crate::solution::{lines, Answer, Contribution, Example, Part, Solution};
+use crate::trace::Level;
+
+const DAY: u8 = 1;
+
+pub struct Day01;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet",
+        part1: Some(142),
+        part2: None,
+    },
+    Example {
+        input: "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen",
+        part1: None,
+        part2: Some(281),
+    },
+];
+
+impl Solution for Day01 {
+    type Model = Vec<String>;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Trebuchet?!"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(lines(input))
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<Answer, SolveError> {
+        sum_lines(lines.iter().map(String::as_str), Part::One)
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> Result<Answer, SolveError> {
+        sum_lines(lines.iter().map(String::as_str), Part::Two)
+    }
+
+    fn explain(&self, lines: &Vec<String>, part: Part) -> Vec<Contribution> {
+        lines.iter().enumerate()
+            .map(|(line_index, line)| Contribution::new(format!("line {} {:?}", line_index + 1, line), extract_number(line, vocabulary(part)) as usize))
+            .collect()
+    }
+
+    fn evaluate_line(&self, line: &str, part: Part) -> Result<String, SolveError> {
+        Ok(extract_number(line, vocabulary(part)).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some((0..size).map(|_| generate_line(rng) + "\n").collect())
+    }
 }
 
-fn sum_lines(lines: Vec<&str>, part: i32) -> i32 {
-    let mut sum = 0;
+// number names sharing a letter, they count as both numbers
+const OVERLAPPING_NAMES: [&str; 8] = ["twone", "oneight", "threeight", "fiveight", "sevenine", "eightwo", "eighthree", "nineight"];
+
+pub fn sum_lines<'a>(lines: impl IntoIterator<Item = &'a str>, part: Part) -> Result<u64, SolveError> {
+    let mut calibration = Calibration::default();
     for line in lines {
-        sum += extract_number(line, part)
+        add_line(&mut calibration, line, part)?;
     }
-    sum
+    Ok(calibration.sum)
 }
 
-fn extract_number(line_ref: &str, part: i32) -> i32 {
-    let mut line = String::from(line_ref);
+/// The calibration sum of `part` over the lines of `reader`, one line in memory at a time.
+///
+/// Lines may end in `\n` or `\r\n`, bytes that are no valid UTF-8 are never digits and are skipped. A sum that no
+/// longer fits is reported as an [`io::ErrorKind::InvalidData`] error naming the line.
+pub fn sum_reader<R: BufRead>(mut reader: R, part: Part) -> io::Result<u64> {
+    let mut calibration = Calibration::default();
+    let mut buffer = Vec::new();
+    while reader.read_until(b'\n', &mut buffer)? > 0 {
+        let line = buffer.strip_suffix(b"\n").unwrap_or(&buffer);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        add_line(&mut calibration, &String::from_utf8_lossy(line), part)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+        buffer.clear();
+    }
+    Ok(calibration.sum)
+}
+
+fn add_line(calibration: &mut Calibration, line: &str, part: Part) -> Result<u32, SolveError> {
+    let number = calibration.add(line, vocabulary(part))?;
+    event!(Level::Debug, DAY, "line", part = part.to_string(), line = calibration.lines, text = line, value = number);
+    Ok(number)
+}
+
+/// A running calibration sum, fed one line at a time, for summing with any vocabulary.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    pub lines: usize,
+    pub sum: u64,
+}
+
+impl Calibration {
+    // adds the calibration value of the next line and returns it, an error when the sum no longer fits
+    pub fn add(&mut self, line: &str, vocabulary: &Vocabulary) -> Result<u32, SolveError> {
+        let number = extract_number(line, vocabulary);
+        self.lines += 1;
+        self.sum = self.sum.checked_add(number as u64)
+            .ok_or_else(|| SolveError::overflow(DAY, &format!("the calibration sum overflows at line {}", self.lines)))?;
+        Ok(number)
+    }
+}
 
-    if part == 2 {
-        line = replace_number_names(&line, false);
-        line = replace_number_names(&line, true);
+// the first and the last digit of the line, digits spelled out with a name of `vocabulary` included
+pub fn extract_number(line: &str, vocabulary: &Vocabulary) -> u32 {
+    let line = if vocabulary.ignore_case { Cow::Owned(line.to_lowercase()) } else { Cow::Borrowed(line) };
+    match (vocabulary.scanner.first(&line), vocabulary.scanner.last(&line)) {
+        (Some(first), Some(last)) => (first * 10 + last) as u32,
+        _ => 0,
     }
+}
 
-    let mut digits_iterator = line.chars().collect::<Vec<_>>().into_iter().flat_map(|char| {
-        if char.is_digit(10) {
-            return Some(char).into_iter();
-        } else {
-            return None.into_iter();
+// part 1 only counts digits, part 2 also counts the english names one to nine
+pub fn vocabulary(part: Part) -> &'static Vocabulary {
+    static DIGITS: OnceLock<Vocabulary> = OnceLock::new();
+    static NAMES: OnceLock<Vocabulary> = OnceLock::new();
+    match part {
+        Part::One => DIGITS.get_or_init(Vocabulary::digits),
+        Part::Two => NAMES.get_or_init(|| Vocabulary::of(Language::English, false)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [Language::English, Language::German, Language::French, Language::Spanish];
+
+    // the names of the digits zero to nine
+    pub fn names(self) -> [&'static str; 10] {
+        match self {
+            Language::English => ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"],
+            Language::German => ["null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"],
+            Language::French => ["zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"],
+            Language::Spanish => ["cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve"],
         }
-    });
-    let first_character = digits_iterator.next();
-    let last_character = digits_iterator.last();
-    if let (Some(first_character), Some(last_character)) = (first_character, last_character) {
-        return String::from_iter([first_character, last_character]).parse().unwrap();
-    } else if let (Some(first_character), None) = (first_character, last_character) {
-        return String::from_iter([first_character, first_character]).parse().unwrap();
-    } else {
-        return 0;
     }
 }
 
-fn replace_number_names(line_ref: &str, reverse: bool) -> String {
-    let line = line_ref.to_string();
+/// The spelled out digits counted besides the digits themselves, each name with the digit it stands for.
+///
+/// Names may contain any characters. With `ignore_case` the names and the lines are compared in lower case, with
+/// `unicode_digits` the decimal digits of all scripts of [`UNICODE_DIGITS_VERSION`] count, e.g. the Arabic-Indic `٣`,
+/// otherwise only `0` to `9`.
+#[derive(Debug, Clone)]
+pub struct Vocabulary {
+    names: Vec<(String, u8)>,
+    ignore_case: bool,
+    unicode_digits: bool,
+    scanner: Scanner,
+}
 
-    let mut base_range = 0..line.len();
-    let mut base_range_reverse = (0..line.len()).rev();
-    let range = if !reverse {
-        &mut base_range as &mut dyn Iterator<Item = _>
-    } else {
-        &mut base_range_reverse
-    };
+#[derive(Debug)]
+pub enum VocabularyError {
+    Io { path: String, error: io::Error },
+    Syntax { line: usize, text: String, message: &'static str },
+}
 
-    for position in range {
-        let char = line.chars().nth(position);
-        if let Some(char) = char {
-            if char.is_digit(10) {
-                return line;
-            }
+impl fmt::Display for VocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VocabularyError::Io { path, error } => write!(f, "cannot read vocabulary '{}': {}", path, error),
+            VocabularyError::Syntax { line, text, message } => write!(f, "vocabulary line {}: {} '{}'", line, message, text),
         }
+    }
+}
+
+impl Vocabulary {
+    pub fn new(names: impl IntoIterator<Item = (String, u8)>) -> Vocabulary {
+        let names = names.into_iter().collect::<Vec<_>>();
+        let scanner = Scanner::new(&names, false);
+        Vocabulary { names, ignore_case: false, unicode_digits: false, scanner }
+    }
+
+    // only the digits themselves
+    pub fn digits() -> Vocabulary {
+        Vocabulary::new([])
+    }
+
+    // the names of one to nine in `language`, and with `zero` the name of zero
+    pub fn of(language: Language, zero: bool) -> Vocabulary {
+        let skip = if zero { 0 } else { 1 };
+        Vocabulary::new(language.names().iter().enumerate().skip(skip).map(|(digit, name)| (name.to_string(), digit as u8)))
+    }
+
+    pub fn ignoring_case(self) -> Vocabulary {
+        let names = self.names.iter().map(|(name, digit)| (name.to_lowercase(), *digit)).collect::<Vec<_>>();
+        Vocabulary { scanner: Scanner::new(&names, self.unicode_digits), names, ignore_case: true, ..self }
+    }
+
+    pub fn with_unicode_digits(self) -> Vocabulary {
+        Vocabulary { scanner: Scanner::new(&self.names, true), unicode_digits: true, ..self }
+    }
+
+    pub fn names(&self) -> &[(String, u8)] {
+        &self.names
+    }
+
+    pub fn load(path: &Path) -> Result<Vocabulary, VocabularyError> {
+        let content = read_to_string(path).map_err(|error| VocabularyError::Io { path: path.display().to_string(), error })?;
+        Vocabulary::parse(&content)
+    }
 
-        for (number, text) in ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"].iter().enumerate() {
-            let start_offset:i32  = if reverse {position as i32 + 1 - text.len() as i32} else {position as i32};
-            if (!reverse && position + text.len() > line.len()) || (reverse && start_offset < 0) {
+    // one `name = digit` per line, `#` starts a comment
+    pub fn parse(content: &str) -> Result<Vocabulary, VocabularyError> {
+        let mut names = Vec::new();
+        for (line_index, line) in content.lines().enumerate() {
+            let syntax = |message| VocabularyError::Syntax { line: line_index + 1, text: line.to_string(), message };
+            let line = line.split_once('#').map_or(line, |(content, _)| content).trim();
+            if line.is_empty() {
                 continue;
             }
-            let (before, tmp) = line.split_at(start_offset as usize).clone();
-            let (value, after) = tmp.split_at(text.len()).clone();
-
-            if value == *text {
-                let mut new_line = String::from(before);
-                new_line.push_str(&(number + 1).to_string());
-                new_line.push_str(after);
-                return new_line;
+            let (name, digit) = line.split_once('=').ok_or_else(|| syntax("expected '<name> = <digit>'"))?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(syntax("missing name"));
+            }
+            let digit = digit.trim().parse::<u8>().ok().filter(|digit| *digit <= 9).ok_or_else(|| syntax("expected a digit from 0 to 9"))?;
+            names.push((name.to_string(), digit));
+        }
+        Ok(Vocabulary::new(names))
+    }
+}
+
+/// Finds the first and the last digit of a line, counting a spelled out number as its digit.
+///
+/// The first digit is the digit or name starting leftmost, the last digit the digit or name ending rightmost; of
+/// names starting (or ending) at the same position the shortest wins. Finding a name never consumes its letters,
+/// so overlapping names count for both ends: `eightwo` is 82, `oneight` is 18 and `twone` is 21, while a line
+/// with a single digit or name uses it twice, `seven` is 77.
+///
+/// The line is scanned once from the start and once from the end, and at every character the digit names are
+/// matched with a trie, forwards from the start and backwards from the end. The tries compare UTF-8 bytes, which
+/// cannot match in the middle of a character as no character's encoding starts inside another's.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    forward: Trie,
+    backward: Trie,
+    unicode_digits: bool,
+}
+
+impl Scanner {
+    pub fn new(names: &[(String, u8)], unicode_digits: bool) -> Scanner {
+        let mut forward = Trie::default();
+        let mut backward = Trie::default();
+        for (name, digit) in names {
+            forward.insert(name.bytes(), *digit);
+            backward.insert(name.bytes().rev(), *digit);
+        }
+        Scanner { forward, backward, unicode_digits }
+    }
+
+    pub fn first(&self, line: &str) -> Option<u8> {
+        let bytes = line.as_bytes();
+        line.char_indices().find_map(|(start, character)| {
+            self.digit(character).or_else(|| self.forward.find(bytes[start..].iter().copied()))
+        })
+    }
+
+    pub fn last(&self, line: &str) -> Option<u8> {
+        let bytes = line.as_bytes();
+        line.char_indices().rev().find_map(|(start, character)| {
+            let end = start + character.len_utf8();
+            self.digit(character).or_else(|| self.backward.find(bytes[..end].iter().rev().copied()))
+        })
+    }
+
+    fn digit(&self, character: char) -> Option<u8> {
+        if self.unicode_digits {
+            unicode_digit(character)
+        } else {
+            character.to_digit(10).map(|digit| digit as u8)
+        }
+    }
+}
+
+/// The Unicode version whose decimal digits `unicode_digit` knows, the one of `char::UNICODE_VERSION` in std 1.95.
+pub const UNICODE_DIGITS_VERSION: (u8, u8, u8) = (17, 0, 0);
+
+// the zeros of the decimal digits (category Nd) of `UNICODE_DIGITS_VERSION`, each followed by the digits one to nine
+const UNICODE_ZEROS: [u32; 77] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650,
+    0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50, 0x11D50, 0x11DA0, 0x11DE0, 0x11F50,
+    0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140,
+    0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950, 0x1FBF0,
+];
+
+/// The value of a decimal digit of any script, `None` for other characters.
+pub fn unicode_digit(character: char) -> Option<u8> {
+    let code = character as u32;
+    let zero = match UNICODE_ZEROS.binary_search(&code) {
+        Ok(index) => UNICODE_ZEROS[index],
+        Err(0) => return None,
+        Err(index) => UNICODE_ZEROS[index - 1],
+    };
+    (code - zero < 10).then(|| (code - zero) as u8)
+}
+
+// the nodes of the trie are stored in a list, the root is the first one
+#[derive(Debug, Clone)]
+struct Trie {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: Vec<(u8, usize)>,
+    digit: Option<u8>,
+}
+
+impl Default for Trie {
+    fn default() -> Trie {
+        Trie { nodes: vec![Node::default()] }
+    }
+}
+
+impl Trie {
+    fn insert(&mut self, bytes: impl Iterator<Item = u8>, digit: u8) {
+        let mut node = 0;
+        for byte in bytes {
+            node = match self.child(node, byte) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(Node::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push((byte, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].digit = Some(digit);
+    }
+
+    // the digit of the shortest name `bytes` starts with
+    fn find(&self, bytes: impl Iterator<Item = u8>) -> Option<u8> {
+        let mut node = 0;
+        for byte in bytes {
+            node = self.child(node, byte)?;
+            if let Some(digit) = self.nodes[node].digit {
+                return Some(digit);
             }
         }
+        None
     }
-    return line;
+
+    fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        self.nodes[node].children.iter().find(|(child_byte, _)| *child_byte == byte).map(|(_, child)| *child)
+    }
+}
+
+// random letters, digits, number names and overlapping number names, with at least one digit like every puzzle line
+fn generate_line(rng: &mut Rng) -> String {
+    let digit = |rng: &mut Rng| char::from(b'1' + rng.below(9) as u8);
+    let mut line = String::new();
+    for _ in 0..rng.range(1..=8) {
+        match rng.below(4) {
+            0 => line.push(char::from(b'a' + rng.below(26) as u8)),
+            1 => line.push(digit(rng)),
+            2 => line.push_str(rng.choose::<&str>(&Language::English.names()[1..])),
+            _ => line.push_str(rng.choose::<&str>(&OVERLAPPING_NAMES)),
+        }
+    }
+    if !line.chars().any(|character| character.is_ascii_digit()) {
+        let position = rng.below(line.len() + 1);
+        line.insert(position, digit(rng));
+    }
+    line
 }
 
 #[cfg(test)]
@@ -85,43 +403,290 @@ mod tests {
 
     #[test]
     fn test_extract_number() -> Result<(), &'static str> {
-        assert_eq!(extract_number("12", 1), 12);
-        assert_eq!(extract_number("foo1bar2baz", 1), 12);
-        assert_eq!(extract_number("foo1bar3qux2baz", 1), 12);
-        assert_eq!(extract_number("3", 1), 33);
-        assert_eq!(extract_number("foo3", 1), 33);
-        assert_eq!(extract_number("foo3bar", 1), 33);
-        assert_eq!(extract_number("3bar", 1), 33);
-        assert_eq!(extract_number("three12", 2), 32);
-        assert_eq!(extract_number("12four", 2), 14);
-        assert_eq!(extract_number("6fourmnvkgnthjtnjqkr", 2), 64);
+        assert_eq!(extract_number("12", vocabulary(Part::One)), 12);
+        assert_eq!(extract_number("foo1bar2baz", vocabulary(Part::One)), 12);
+        assert_eq!(extract_number("foo1bar3qux2baz", vocabulary(Part::One)), 12);
+        assert_eq!(extract_number("3", vocabulary(Part::One)), 33);
+        assert_eq!(extract_number("foo3", vocabulary(Part::One)), 33);
+        assert_eq!(extract_number("foo3bar", vocabulary(Part::One)), 33);
+        assert_eq!(extract_number("3bar", vocabulary(Part::One)), 33);
+        assert_eq!(extract_number("three12", vocabulary(Part::Two)), 32);
+        assert_eq!(extract_number("12four", vocabulary(Part::Two)), 14);
+        assert_eq!(extract_number("6fourmnvkgnthjtnjqkr", vocabulary(Part::Two)), 64);
+
+
+        Ok(())
+    }
 
+    #[test]
+    fn test_part_1_example() -> Result<(), &'static str> {
+        assert_eq!(sum_lines(EXAMPLES[0].input.lines(), Part::One), Ok(142));
 
         Ok(())
     }
 
     #[test]
     fn test_part_2_example() -> Result<(), &'static str> {
-        let lines = ["two1nine", "eightwothree", "abcone2threexyz", "xtwone3four", "4nineeightseven2", "zoneight234", "7pqrstsixteen"].to_vec();
+        assert_eq!(sum_lines(EXAMPLES[1].input.lines(), Part::Two), Ok(281));
 
-        assert_eq!(sum_lines(lines, 2), 281);
+        Ok(())
+    }
+
+    // `count` lines of `line`, produced on the fly so the input never exists as a whole
+    struct Repeated {
+        line: &'static [u8],
+        count: usize,
+        position: usize,
+    }
+
+    impl io::Read for Repeated {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            if self.count == 0 {
+                return Ok(0);
+            }
+            let read = (self.line.len() - self.position).min(buffer.len());
+            buffer[..read].copy_from_slice(&self.line[self.position..self.position + read]);
+            self.position += read;
+            if self.position == self.line.len() {
+                (self.count, self.position) = (self.count - 1, 0);
+            }
+            Ok(read)
+        }
+    }
 
+    #[test]
+    fn test_sum_reader() -> Result<(), &'static str> {
+        assert_eq!(sum_reader(EXAMPLES[0].input.as_bytes(), Part::One).ok(), Some(142));
+        assert_eq!(sum_reader(EXAMPLES[1].input.as_bytes(), Part::Two).ok(), Some(281));
+        assert_eq!(sum_reader(&b"two1nine\r\neightwothree\r\n"[..], Part::Two).ok(), Some(29 + 83));
+        assert_eq!(sum_reader(&b"\xff7\xfe\nab\xc3\n"[..], Part::One).ok(), Some(77));
+        assert_eq!(sum_reader(&b""[..], Part::One).ok(), Some(0));
+
+        let reader = io::BufReader::new(Repeated { line: b"x9twone8y\n", count: 200_000, position: 0 });
+        assert_eq!(sum_reader(reader, Part::Two).ok(), Some(200_000 * 98));
         Ok(())
     }
 
     #[test]
-    fn test_replace_number_names() -> Result<(), &'static str> {
-        assert_eq!(replace_number_names("two1nine", false), "21nine");
-        assert_eq!(replace_number_names("1twonine", false), "1twonine");
-        assert_eq!(replace_number_names("eightwothree", false), "8wothree");
-
-        assert_eq!(replace_number_names("1twonine", true), "1two9");
-        assert_eq!(replace_number_names("1twoniner", true), "1two9r");
-        assert_eq!(replace_number_names("1two9", true), "1two9");
+    fn test_calibration_overflow() -> Result<(), &'static str> {
+        let mut calibration = Calibration { lines: 2, sum: u64::MAX - 12 };
+        assert_eq!(calibration.add("1two", vocabulary(Part::Two)), Ok(12));
+        assert_eq!(calibration.sum, u64::MAX);
+
+        let error = calibration.add("x1", vocabulary(Part::One)).err().ok_or("the sum cannot overflow")?;
+        assert_eq!(error.to_string(), "day 1: the calibration sum overflows at line 4");
+        assert_eq!(calibration.sum, u64::MAX);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scanner() -> Result<(), &'static str> {
+        let scanner = &vocabulary(Part::Two).scanner;
+
+        assert_eq!(scanner.first("two1nine"), Some(2));
+        assert_eq!(scanner.first("1twonine"), Some(1));
+        assert_eq!(scanner.first("eightwothree"), Some(8));
+        assert_eq!(scanner.last("1twonine"), Some(9));
+        assert_eq!(scanner.last("1twoniner"), Some(9));
+        assert_eq!(scanner.last("1two9"), Some(9));
+        assert_eq!(scanner.last("eightwo"), Some(2));
+        assert_eq!((scanner.first("abc"), scanner.last("")), (None, None));
+
+        let digits = Scanner::new(&[], false);
+        assert_eq!((digits.first("two1nine"), digits.last("two1nine")), (Some(1), Some(1)));
+        Ok(())
+    }
+
+    // every digit and name occurrence as start, end and digit, by trying every name at every position
+    fn occurrences(line: &str, vocabulary: &Vocabulary) -> Vec<(usize, usize, u8)> {
+        let mut occurrences = Vec::new();
+        for (start, character) in line.char_indices() {
+            if let Some(digit) = vocabulary.scanner.digit(character) {
+                occurrences.push((start, start + character.len_utf8(), digit));
+            }
+            for (name, digit) in vocabulary.names() {
+                if line[start..].starts_with(name.as_str()) {
+                    occurrences.push((start, start + name.len(), *digit));
+                }
+            }
+        }
+        occurrences
+    }
+
+    fn reference_number(line: &str, vocabulary: &Vocabulary) -> u32 {
+        let occurrences = occurrences(line, vocabulary);
+        let first = occurrences.iter().min_by_key(|(start, end, _)| (*start, *end));
+        let last = occurrences.iter().max_by_key(|(start, end, _)| (*end, *start));
+        match (first, last) {
+            (Some((_, _, first)), Some((_, _, last))) => (first * 10 + last) as u32,
+            _ => 0,
+        }
+    }
+
+    // `word` appended to `line`, sharing its first `overlap` bytes with the end of `line`
+    fn overlapped(line: &str, word: &str, overlap: usize) -> Option<String> {
+        (overlap < word.len() && word.is_char_boundary(overlap) && line.ends_with(&word[..overlap])).then(|| format!("{}{}", line, &word[overlap..]))
+    }
+
+    #[test]
+    fn test_overlapping_names() -> Result<(), &'static str> {
+        assert_eq!(extract_number("eightwo", vocabulary(Part::Two)), 82);
+        assert_eq!(extract_number("oneight", vocabulary(Part::Two)), 18);
+        assert_eq!(extract_number("twone", vocabulary(Part::Two)), 21);
+        assert_eq!(extract_number("seven", vocabulary(Part::Two)), 77);
+        assert_eq!(extract_number("sevenine", vocabulary(Part::Two)), 79);
+        assert_eq!(extract_number("eighthree", vocabulary(Part::Two)), 83);
+        assert_eq!(extract_number("twoneight", vocabulary(Part::Two)), 28);
+        assert_eq!(extract_number("3oneight", vocabulary(Part::Two)), 38);
+        Ok(())
+    }
+
+    // every pair and triple of names, overlapping by any number of letters they share, with and without digits around
+    #[test]
+    fn test_overlapping_names_exhaustively() -> Result<(), &'static str> {
+        for language in Language::ALL {
+            let vocabulary = Vocabulary::of(language, true);
+            let names = language.names();
+            let mut lines = Vec::new();
+            for first in names {
+                for second in names {
+                    for overlap in 0..second.len() {
+                        let Some(pair) = overlapped(first, second, overlap) else { continue };
+                        for third in names {
+                            lines.extend((0..third.len()).filter_map(|overlap| overlapped(&pair, third, overlap)));
+                        }
+                        lines.push(pair);
+                    }
+                }
+            }
+            assert!(lines.len() > 1000, "{:?}", language);
+
+            for line in lines {
+                for line in [line.clone(), format!("x{}y", line), format!("5{}", line), format!("{}7", line)] {
+                    assert_eq!(extract_number(&line, &vocabulary), reference_number(&line, &vocabulary), "{:?} {}", language, line);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_languages() -> Result<(), &'static str> {
+        assert_eq!(extract_number("achtzweiundvierzig", &Vocabulary::of(Language::German, false)), 84);
+        assert_eq!(extract_number("sechsfünf", &Vocabulary::of(Language::German, false)), 65);
+        assert_eq!(extract_number("zérotroisdeux", &Vocabulary::of(Language::French, true)), 2);
+        assert_eq!(extract_number("cuatro y tres", &Vocabulary::of(Language::Spanish, false)), 43);
+
+        assert_eq!(extract_number("zero3", vocabulary(Part::Two)), 33);
+        assert_eq!(extract_number("zero3", &Vocabulary::of(Language::English, true)), 3);
+        for language in Language::ALL {
+            let vocabulary = Vocabulary::of(language, true);
+            for (digit, name) in language.names().iter().enumerate() {
+                assert_eq!(extract_number(&format!("x{}x", name), &vocabulary), digit as u32 * 11, "{}", name);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_multibyte_lines() -> Result<(), &'static str> {
+        assert_eq!(extract_number("é1ü2ß", vocabulary(Part::One)), 12);
+        assert_eq!(extract_number("😀one😀", vocabulary(Part::Two)), 11);
+        assert_eq!(extract_number("ñtwoñ3ñ", vocabulary(Part::Two)), 23);
+        assert_eq!(extract_number("日本eightwo語", vocabulary(Part::Two)), 82);
+        assert_eq!(extract_number("fünfzig4", &Vocabulary::of(Language::German, false)), 54);
+        assert_eq!(extract_number("ünf1", &Vocabulary::of(Language::German, false)), 11);
+        assert_eq!(extract_number("deuxzérozéro", &Vocabulary::of(Language::French, true)), 20);
+        assert_eq!(extract_number("İseven", &Vocabulary::of(Language::English, false).ignoring_case()), 77);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unicode_digits() -> Result<(), &'static str> {
+        let unicode = Vocabulary::of(Language::English, false).with_unicode_digits();
+
+        assert_eq!(extract_number("٣abc٧", vocabulary(Part::Two)), 0);
+        assert_eq!(extract_number("٣abc٧", &unicode), 37);
+        assert_eq!(extract_number("x٣5", &unicode), 35);
+        assert_eq!(extract_number("１two", &unicode), 12);
+        assert_eq!(extract_number("nine۴", &unicode.clone().ignoring_case()), 94);
+        assert_eq!(extract_number("४२", &Vocabulary::digits().with_unicode_digits()), 42);
+
+        assert_eq!(unicode_digit('٠'), Some(0));
+        assert_eq!(unicode_digit('٩'), Some(9));
+        assert_eq!(unicode_digit('\u{1D7FF}'), Some(9));
+        assert_eq!(unicode_digit('Ⅻ'), None);
+        assert_eq!(unicode_digit('/'), None);
+        assert_eq!(unicode_digit('a'), None);
+        assert_eq!((0..10).map(|digit| unicode_digit(char::from(b'0' + digit))).collect::<Vec<_>>(), (0..10).map(Some).collect::<Vec<_>>());
+        Ok(())
+    }
+
+    // every digit of the table is numeric for std, the ASCII ones agree with `to_digit`, and the character before a run
+    // of ten is no digit
+    #[test]
+    fn test_unicode_zeros() -> Result<(), &'static str> {
+        // older toolchains know fewer digits than the table, so only a new enough std can be compared with it
+        let std_knows_table = char::UNICODE_VERSION >= UNICODE_DIGITS_VERSION;
+        assert!(UNICODE_ZEROS.windows(2).all(|pair| pair[0] + 10 <= pair[1]));
+        for zero in UNICODE_ZEROS {
+            for offset in 0..10 {
+                let character = char::from_u32(zero + offset).ok_or("not a character")?;
+                assert!(!std_knows_table || character.is_numeric(), "{:#X}", zero + offset);
+                assert_eq!(unicode_digit(character), Some(offset as u8));
+            }
+            // Myanmar Pao and Eastern Pwo Karen digits follow each other
+            if !UNICODE_ZEROS.contains(&(zero - 10)) {
+                assert_eq!(char::from_u32(zero - 1).and_then(unicode_digit), None, "{:#X}", zero - 1);
+            }
+        }
+        for character in '\0'..='\u{7F}' {
+            assert_eq!(unicode_digit(character), character.to_digit(10).map(|digit| digit as u8));
+        }
+        // Kawi and Nag Mundari were added in Unicode 15
+        assert_eq!((unicode_digit('\u{11F57}'), unicode_digit('\u{1E4F3}')), (Some(7), Some(3)));
+        Ok(())
+    }
+
+    // random lines of multibyte characters, digits and names never panic and match the reference
+    #[test]
+    fn test_random_multibyte_lines() -> Result<(), &'static str> {
+        let pieces = ["é", "ü", "日", "😀", "٣", "١", "３", "7", "x", "one", "fünf", "eight", "two", "zéro", "İ"];
+        let vocabularies = [
+            Vocabulary::of(Language::English, true),
+            Vocabulary::of(Language::German, false).with_unicode_digits(),
+            Vocabulary::of(Language::French, true).with_unicode_digits(),
+        ];
+        let mut rng = Rng::new(24);
+        for _ in 0..2000 {
+            let line = (0..rng.range(0..=8)).map(|_| *rng.choose(&pieces)).collect::<String>();
+            for vocabulary in &vocabularies {
+                assert_eq!(extract_number(&line, vocabulary), reference_number(&line, vocabulary), "{}", line);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ignore_case() -> Result<(), &'static str> {
+        assert_eq!(extract_number("Two1NINE", vocabulary(Part::Two)), 11);
+        assert_eq!(extract_number("Two1NINE", &Vocabulary::of(Language::English, false).ignoring_case()), 29);
+        assert_eq!(extract_number("FÜNF1", &Vocabulary::of(Language::German, false).ignoring_case()), 51);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vocabulary_file() -> Result<(), &'static str> {
+        let vocabulary = Vocabulary::parse("# dutch\neen = 1\ntwee = 2 # two\n\nnul=0\n").unwrap();
+
+        assert_eq!(vocabulary.names(), [(String::from("een"), 1), (String::from("twee"), 2), (String::from("nul"), 0)]);
+        assert_eq!(extract_number("tweeeen", &vocabulary), 21);
+
+        let error = Vocabulary::parse("een = 1\ntwee").unwrap_err();
+        assert_eq!(error.to_string(), "vocabulary line 2: expected '<name> = <digit>' 'twee'");
+        assert!(matches!(Vocabulary::parse("tien = 10"), Err(VocabularyError::Syntax { line: 1, .. })));
+        assert!(matches!(Vocabulary::parse(" = 1"), Err(VocabularyError::Syntax { message: "missing name", .. })));
+        assert!(matches!(Vocabulary::load(Path::new("missing.txt")), Err(VocabularyError::Io { .. })));
         Ok(())
     }
-}
-pub(crate) fn run() {
-    puzzle_01(1);
-    puzzle_01(2);
 }
@@ -1,71 +1,180 @@
-use std::collections::HashMap;
-use std::fs::read_to_string;
+use crate::error::{ParseError, SolveError};
+use crate::generate::Rng;
+use crate::solution::{Answer, Contribution, Example, Part, Solution};
 
-fn puzzle(part: i32) {
-    let string = read_to_string("./02_data.txt").unwrap();
-    let lines = string.lines().collect::<Vec<_>>();
+const DAY: u8 = 2;
 
-    let bag = HashMap::from([
-        ("red", 12),
-        ("green", 13),
-        ("blue", 14),
-    ]);
+pub const BAG: CubeSet = CubeSet { red: 12, green: 13, blue: 14 };
 
-    let sum = calculate_lines(lines, &bag, part);
-    println!("day 2 part {}: {}", part, sum)
-}
+pub struct Day02;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#,
+        part1: Some(8),
+        part2: Some(2286),
+    },
+];
+
+impl Solution for Day02 {
+    type Model = Vec<Game>;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Game>, ParseError> {
+        parse_games(input)
+    }
+
+    fn part1(&self, games: &Vec<Game>) -> Result<Answer, SolveError> {
+        Ok(sum_of_possible_ids(games, &BAG) as Answer)
+    }
+
+    fn part2(&self, games: &Vec<Game>) -> Result<Answer, SolveError> {
+        Ok(sum_of_powers(games)? as Answer)
+    }
+
+    fn explain(&self, games: &Vec<Game>, part: Part) -> Vec<Contribution> {
+        games.iter().map(|game| match part {
+            Part::One if game.is_possible(&BAG) => Contribution::new(format!("game {}: possible", game.id), game.id),
+            Part::One => Contribution::new(format!("game {}: impossible", game.id), 0),
+            Part::Two => {
+                // only explained once part 2 is solved, so the powers fit
+                let bag = game.minimal_bag();
+                Contribution::new(format!("game {}: {} red, {} green, {} blue", game.id, bag.red, bag.green, bag.blue), bag.power().unwrap_or_default())
+            }
+        }).collect()
+    }
 
-fn calculate_lines(lines: Vec<&str>, bag: &HashMap<&str, usize>, part: i32) -> usize {
-    let mut sum: usize = 0;
-    for line in lines {
-        let result = analyse_line(line, &bag, part == 1);
-        if let Some(result) = result {
-            sum += result;
+    fn evaluate_line(&self, line: &str, part: Part) -> Result<String, SolveError> {
+        let game = Game::parse(line)?;
+        let bag = game.minimal_bag();
+        Ok(match part {
+            Part::One if game.is_possible(&BAG) => format!("game {} is possible", game.id),
+            Part::One => format!("game {} is impossible", game.id),
+            Part::Two => format!("{} red, {} green, {} blue, power {}", bag.red, bag.green, bag.blue, game_power(&game)?),
+        })
+    }
+
+    // up to 20 cubes of a color, so that some games do not fit into the bag
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for id in 1..=size {
+            let draws = (0..rng.range(1..=6)).map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                let count = rng.range(1..=3);
+                colors[..count].iter().map(|color| format!("{} {}", rng.range(1..=20), color)).collect::<Vec<_>>().join(", ")
+            }).collect::<Vec<_>>();
+            input.push_str(&format!("Game {}: {}\n", id, draws.join("; ")));
         }
+        Some(input)
     }
-    sum
 }
 
-fn analyse_line(line_ref: &str, bag: &HashMap<&str, usize>, limit:bool) -> Option<usize> {
-    let mut split1 = line_ref.trim().split(':');
-    let game = split1.next().unwrap().trim();
-    let game_id: usize = game.split(' ').nth(1).unwrap().parse().unwrap();
-    let split2 = split1.next().unwrap().trim().split(';');
-    let mut possible = true;
-    let mut minimal_bag = HashMap::<&str, usize>::new();
-    for part in split2 {
-        let split3 = part.trim().split(',');
-        for element in split3 {
-            let mut split4 = element.trim().split(' ');
-            let number: usize = split4.next().unwrap().trim().parse().unwrap();
-            let color = split4.next().unwrap().trim();
-            if limit {
-                possible &= &number <= bag.get(color).unwrap();
-            } else {
-                let option = minimal_bag.get(color);
-                if &number > option.unwrap_or(&0) {
-                    minimal_bag.insert(color, number);
-                }
-            }
-            if !possible {
-                break;
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CubeSet {
+    pub red: usize,
+    pub green: usize,
+    pub blue: usize,
+}
+
+impl CubeSet {
+    pub fn fits_into(&self, bag: &CubeSet) -> bool {
+        self.red <= bag.red && self.green <= bag.green && self.blue <= bag.blue
+    }
+
+    pub fn max(&self, other: &CubeSet) -> CubeSet {
+        CubeSet { red: self.red.max(other.red), green: self.green.max(other.green), blue: self.blue.max(other.blue) }
+    }
+
+    // `None` if the product does not fit
+    pub fn power(&self) -> Option<usize> {
+        self.red.checked_mul(self.green)?.checked_mul(self.blue)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    pub id: usize,
+    pub draws: Vec<CubeSet>,
+}
+
+impl Game {
+    pub fn parse(line: &str) -> Result<Game, ParseError> {
+        let (game, draws) = line.split_once(':')
+            .ok_or_else(|| ParseError::new(DAY, line, line, "expected 'Game <id>: <draws>'"))?;
+        let game = game.trim();
+        let id = game.split(' ').nth(1)
+            .ok_or_else(|| ParseError::new(DAY, line, game, "missing game id"))?;
+        let id: usize = id.parse()
+            .map_err(|_| ParseError::new(DAY, line, id, "invalid game id"))?;
+
+        let mut parsed_draws = Vec::new();
+        for draw in draws.trim().split(';') {
+            let mut cubes = CubeSet::default();
+            for element in draw.trim().split(',') {
+                let mut split = element.trim().split(' ');
+                let number = split.next().unwrap_or_default().trim();
+                let number: usize = number.parse()
+                    .map_err(|_| ParseError::new(DAY, line, number, "invalid number of cubes"))?;
+                let color = split.next()
+                    .ok_or_else(|| ParseError::new(DAY, line, element, "missing color"))?.trim();
+                let count = match color {
+                    "red" => &mut cubes.red,
+                    "green" => &mut cubes.green,
+                    "blue" => &mut cubes.blue,
+                    _ => return Err(ParseError::new(DAY, line, color, "unknown color")),
+                };
+                *count = count.checked_add(number)
+                    .ok_or_else(|| ParseError::new(DAY, line, element.trim(), "too many cubes of a color in a draw"))?;
             }
+            parsed_draws.push(cubes);
         }
-        if !possible {
-            break;
-        }
+        Ok(Game { id, draws: parsed_draws })
     }
-    return if limit {if possible {
-        Some(game_id)
-    } else {
-        None
-    }} else {
-        let mut power :usize = 1;
-        for (_, number) in minimal_bag {
-            power *= number;
-        }
-        Some(power)
+
+    pub fn is_possible(&self, bag: &CubeSet) -> bool {
+        self.draws.iter().all(|draw| draw.fits_into(bag))
     }
+
+    // the fewest cubes of each color the game could have been played with
+    pub fn minimal_bag(&self) -> CubeSet {
+        self.draws.iter().fold(CubeSet::default(), |bag, draw| bag.max(draw))
+    }
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    input.lines().enumerate()
+        .map(|(line_index, line)| Game::parse(line).map_err(|error| error.at_line(line_index + 1)))
+        .collect()
+}
+
+pub fn sum_of_possible_ids(games: &[Game], bag: &CubeSet) -> usize {
+    games.iter().filter(|game| game.is_possible(bag)).map(|game| game.id).sum()
+}
+
+pub fn sum_of_powers(games: &[Game]) -> Result<usize, SolveError> {
+    games.iter().try_fold(0usize, |sum, game| {
+        sum.checked_add(game_power(game)?).ok_or_else(|| SolveError::overflow(DAY, "the sum of the powers overflows"))
+    })
+}
+
+fn game_power(game: &Game) -> Result<usize, SolveError> {
+    game.minimal_bag().power().ok_or_else(|| SolveError::overflow(DAY, &format!("the power of game {} overflows", game.id)))
 }
 
 #[cfg(test)]
@@ -74,59 +183,65 @@ mod tests {
 
     #[test]
     fn test_possible_game() -> Result<(), &'static str> {
-        let bag = HashMap::from([
-            ("red", 12),
-            ("green", 13),
-            ("blue", 14),
-        ]);
+        let game = Game::parse("Game 1: 13 green, 3 red; 4 red, 9 green, 4 blue; 9 green, 10 red, 2 blue").unwrap();
 
-        assert_eq!(analyse_line("Game 1: 13 green, 3 red; 4 red, 9 green, 4 blue; 9 green, 10 red, 2 blue", &bag, true), Some(1));
+        assert_eq!(game.draws[1], CubeSet { red: 4, green: 9, blue: 4 });
+        assert!(game.is_possible(&BAG));
+        assert!(!game.is_possible(&CubeSet { red: 12, green: 12, blue: 14 }));
 
         Ok(())
     }
 
     #[test]
     fn test_id_sum_of_possible_game() -> Result<(), &'static str> {
-        let bag = HashMap::from([
-            ("red", 12),
-            ("green", 13),
-            ("blue", 14),
-        ]);
-        let lines = ["Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"].to_vec();
-
-        assert_eq!(calculate_lines(lines, &bag, 1), 8);
+        assert_eq!(sum_of_possible_ids(&parse_games(EXAMPLES[0].input).unwrap(), &BAG), 8);
 
         Ok(())
     }
     #[test]
     fn test_calculate_power() -> Result<(), &'static str> {
-        let bag = HashMap::<&str, usize>::new();
+        let game = Game::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
 
-        assert_eq!(analyse_line("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", &bag, false), Some(48));
+        assert_eq!(game.minimal_bag(), CubeSet { red: 4, green: 2, blue: 6 });
+        assert_eq!(game.minimal_bag().power(), Some(48));
 
         Ok(())
     }
 
     #[test]
     fn test_power_of_lines() -> Result<(), &'static str> {
-        let bag = HashMap::<&str, usize>::new();
-        let lines = ["Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
-        "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-        "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"].to_vec();
-
-        assert_eq!(calculate_lines(lines, &bag, 2), 2286);
+        assert_eq!(sum_of_powers(&parse_games(EXAMPLES[0].input).unwrap()), Ok(2286));
 
         Ok(())
     }
-}
 
-pub(crate) fn run() {
-    puzzle(1);
-    puzzle(2);
+    #[test]
+    fn test_overflow() {
+        let game = Game::parse("Game 7: 4294967296 red, 4294967296 green, 1 blue").unwrap();
+        assert_eq!(game.minimal_bag().power(), None);
+        assert_eq!(sum_of_powers(&[game]), Err(SolveError::overflow(DAY, "the power of game 7 overflows")));
+
+        let games = parse_games("Game 1: 4294967295 red, 4294967295 green, 1 blue\nGame 2: 4294967295 red, 4294967295 green, 1 blue").unwrap();
+        assert_eq!(sum_of_powers(&games), Err(SolveError::overflow(DAY, "the sum of the powers overflows")));
+
+        let error = Game::parse("Game 1: 18446744073709551615 red, 1 red").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (35, "1 red"));
+    }
+
+    #[test]
+    fn test_parse_errors() -> Result<(), &'static str> {
+        assert_eq!(parse_games("Game 1: 3 red\nGame 2: 3 red, x blue"), Err(ParseError {
+            day: 2,
+            line: 2,
+            column: 16,
+            text: String::from("x"),
+            message: String::from("invalid number of cubes"),
+        }));
+        assert_eq!(Game::parse("Game 1 3 red").unwrap_err().message, "expected 'Game <id>: <draws>'");
+        assert_eq!(Game::parse("Game one: 3 red").unwrap_err().column, 6);
+        assert_eq!(Game::parse("Game 1: 3").unwrap_err().message, "missing color");
+        assert_eq!(Game::parse("Game 1: 3 pink").unwrap_err().column, 11);
+
+        Ok(())
+    }
 }
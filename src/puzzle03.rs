@@ -1,124 +1,249 @@
-use std::fs::read_to_string;
 use std::ops::RangeInclusive;
 
-fn puzzle(part: i32) {
-    let string = read_to_string("./03_data.txt").unwrap();
-    let lines = string.lines().collect::<Vec<_>>();
+use crate::error::{ParseError, SolveError};
+use crate::generate::Rng;
+use crate::solution::{Answer, Contribution, Example, Part, Solution};
+
+const DAY: u8 = 3;
+
+pub struct Day03;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: r#"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598.."#,
+        part1: Some(4361),
+        part2: Some(467835),
+    },
+];
+
+impl Solution for Day03 {
+    type Model = Schematic;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
 
-    let sum = analyze(lines, part);
-    println!("day 3 part {}: {}", part, sum)
-}
+    fn title(&self) -> &'static str {
+        "Gear Ratios"
+    }
 
-fn analyze(lines: Vec<&str>, part: i32) -> usize {
-    let mut symbol_locations = lines.iter().map(|line| symbol_locations(line)).collect::<Vec<_>>();
-    let mut sum: usize = 0;
-    for (line_index, line) in lines.iter().enumerate() {
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
 
-        for number_group in number_groups(line) {
-            let mut has_symbol = false;
+    fn parse(&self, input: &str) -> Result<Schematic, ParseError> {
+        Schematic::parse(input)
+    }
 
-            if line_index > 0 {
-                has_symbol |= has_symbol_in_range(&number_group, &mut symbol_locations[line_index - 1]);
-            }
-            has_symbol |= has_symbol_in_range(&number_group, &mut symbol_locations[line_index]);
-            if line_index < lines.len() - 1 {
-                has_symbol |= has_symbol_in_range(&number_group, &mut symbol_locations[line_index + 1]);
-            }
-            if has_symbol && part == 1 {
-                sum += number_group.number
-            }
+    fn part1(&self, schematic: &Schematic) -> Result<Answer, SolveError> {
+        Ok(sum_of_part_numbers(schematic) as Answer)
+    }
+
+    fn part2(&self, schematic: &Schematic) -> Result<Answer, SolveError> {
+        Ok(sum_of_gear_ratios(schematic)? as Answer)
+    }
+
+    // rows and columns are counted from 1 like in an editor
+    fn explain(&self, schematic: &Schematic, part: Part) -> Vec<Contribution> {
+        match part {
+            Part::One => part_numbers(schematic).into_iter().map(|(row, number_group)| {
+                Contribution::new(format!("part number {} at row {}, column {}", number_group.number, row + 1, number_group.start + 1), number_group.number)
+            }).collect(),
+            // only explained once part 2 is solved, so the ratios fit
+            Part::Two => gears(schematic).into_iter().map(|(row, symbol, first, second)| {
+                let ratio = gear_ratio(row, symbol, first, second).unwrap_or_default();
+                Contribution::new(format!("gear at row {}, column {}: {} * {}", row + 1, symbol.column + 1, first.number, second.number), ratio)
+            }).collect(),
         }
     }
 
-    for symbol_locations_per_line in symbol_locations {
-        for symbol_location in symbol_locations_per_line {
-            if symbol_location.character == '*' {
-                if part == 2 && symbol_location.gears.len() == 2 {
-                    sum += symbol_location.gears[0] * symbol_location.gears[1];
+    // a square schematic with `size` rows of `size` columns
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut row = String::new();
+            while row.len() < size {
+                let after_number = row.ends_with(|character: char| character.is_ascii_digit());
+                match rng.below(100) {
+                    0..=14 if !after_number => {
+                        let length = rng.range(1..=3).min(size - row.len());
+                        row.push_str(&rng.range(10usize.pow(length as u32 - 1)..=10usize.pow(length as u32) - 1).to_string());
+                    }
+                    15..=24 => row.push(*rng.choose(&SYMBOLS)),
+                    _ => row.push('.'),
                 }
             }
+            input.push_str(&row);
+            input.push('\n');
+        }
+        Some(input)
+    }
+}
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '-', '&'];
+
+/// The numbers and symbols of the engine schematic, indexed by row.
+#[derive(Debug, PartialEq)]
+pub struct Schematic {
+    pub numbers: Vec<Vec<NumberGroup>>,
+    pub symbols: Vec<Vec<Symbol>>,
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Result<Schematic, ParseError> {
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        for (line_index, line) in input.lines().enumerate() {
+            numbers.push(number_groups(line).map_err(|error| error.at_line(line_index + 1))?);
+            symbols.push(symbol_locations(line).map_err(|error| error.at_line(line_index + 1))?);
         }
+        Ok(Schematic { numbers, symbols })
     }
 
-    sum
+    // the rows directly above, at and below `row`
+    fn neighbor_rows(&self, row: usize) -> RangeInclusive<usize> {
+        row.saturating_sub(1)..=(row + 1).min(self.numbers.len() - 1)
+    }
+}
+
+pub fn sum_of_part_numbers(schematic: &Schematic) -> usize {
+    part_numbers(schematic).iter().map(|(_, number_group)| number_group.number).sum()
+}
+
+pub fn sum_of_gear_ratios(schematic: &Schematic) -> Result<usize, SolveError> {
+    gears(schematic).into_iter().try_fold(0usize, |sum, (row, symbol, first, second)| {
+        sum.checked_add(gear_ratio(row, symbol, first, second)?).ok_or_else(|| SolveError::overflow(DAY, "the sum of the gear ratios overflows"))
+    })
+}
+
+fn gear_ratio(row: usize, symbol: &Symbol, first: &NumberGroup, second: &NumberGroup) -> Result<usize, SolveError> {
+    first.number.checked_mul(second.number).ok_or_else(|| {
+        SolveError::overflow(DAY, &format!("the ratio of the gear at row {}, column {} overflows", row + 1, symbol.column + 1))
+    })
+}
+
+// the numbers next to a symbol with their row
+pub fn part_numbers(schematic: &Schematic) -> Vec<(usize, &NumberGroup)> {
+    let mut part_numbers = Vec::new();
+    for (row, number_groups) in schematic.numbers.iter().enumerate() {
+        for number_group in number_groups {
+            if schematic.neighbor_rows(row).any(|neighbor| has_symbol_in_range(number_group, &schematic.symbols[neighbor])) {
+                part_numbers.push((row, number_group));
+            }
+        }
+    }
+    part_numbers
 }
 
-fn has_symbol_in_range(number_group: &NumberGroup, symbol_locations: &mut Vec<Symbol>) -> bool {
-    let start = if number_group.start == 0 { 0 } else { number_group.start - 1 };
-    let end = number_group.end + 1;
-    let range = RangeInclusive::<usize>::new(start, end);
-    for symbol_location in symbol_locations {
-        if range.contains(&symbol_location.column) {
-            if symbol_location.character == '*' {
-                symbol_location.gears.push(number_group.number);
+// the `*` symbols next to exactly two numbers with their row and both numbers
+pub fn gears(schematic: &Schematic) -> Vec<(usize, &Symbol, &NumberGroup, &NumberGroup)> {
+    let mut gears = Vec::new();
+    for (row, symbols) in schematic.symbols.iter().enumerate() {
+        for symbol in symbols.iter().filter(|symbol| symbol.character == '*') {
+            let numbers = schematic.neighbor_rows(row)
+                .flat_map(|neighbor| schematic.numbers[neighbor].iter())
+                .filter(|number_group| number_group.is_adjacent(symbol.column))
+                .collect::<Vec<_>>();
+            if let [first, second] = numbers[..] {
+                gears.push((row, symbol, first, second));
             }
-            return true;
         }
     }
-    false
+    gears
+}
+
+pub fn has_symbol_in_range(number_group: &NumberGroup, symbol_locations: &[Symbol]) -> bool {
+    symbol_locations.iter().any(|symbol_location| number_group.is_adjacent(symbol_location.column))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NumberGroup {
+    pub start: usize,
+    pub end: usize,
+    pub number: usize,
 }
 
-struct NumberGroup {
-    start: usize,
-    end: usize,
-    number: usize,
+impl NumberGroup {
+    // whether `column` is within the number or diagonally next to it
+    pub fn is_adjacent(&self, column: usize) -> bool {
+        RangeInclusive::new(self.start.saturating_sub(1), self.end + 1).contains(&column)
+    }
 }
 
-fn number_groups(line: &str) -> Vec<NumberGroup> {
+// the schematic is a grid of ASCII characters, which makes the byte offsets of a line its columns
+fn check_ascii(line: &str) -> Result<(), ParseError> {
+    match line.char_indices().find(|(_, character)| !character.is_ascii()) {
+        Some((index, character)) => Err(ParseError::new(DAY, line, &line[index..index + character.len_utf8()], "unexpected non-ASCII character")),
+        None => Ok(()),
+    }
+}
+
+pub fn number_groups(line: &str) -> Result<Vec<NumberGroup>, ParseError> {
+    check_ascii(line)?;
     let mut number_groups = Vec::<NumberGroup>::new();
     let mut start: Option<usize> = None;
 
-    for (index, character) in line.chars().enumerate() {
-        if character.is_numeric() {
+    for (index, character) in line.char_indices() {
+        if character.is_ascii_digit() {
             if start.is_none() {
                 start = Some(index);
             }
         } else {
-            start = check_end_of_number(start, index, line, &mut number_groups);
+            start = check_end_of_number(start, index, line, &mut number_groups)?;
         }
     }
-    _ = check_end_of_number(start, line.len(), line, &mut number_groups);
-    number_groups
+    _ = check_end_of_number(start, line.len(), line, &mut number_groups)?;
+    Ok(number_groups)
 }
 
-fn check_end_of_number(start: Option<usize>, index: usize, line: &str, number_groups: &mut Vec<NumberGroup>) -> Option<usize> {
-    if start.is_some() {
+fn check_end_of_number(start: Option<usize>, index: usize, line: &str, number_groups: &mut Vec<NumberGroup>) -> Result<Option<usize>, ParseError> {
+    if let Some(start) = start {
+        let text = &line[start..index];
         number_groups.push(NumberGroup {
-            start: start.unwrap(),
+            start,
             end: index - 1,
-            number: line[start.unwrap()..index].parse().unwrap(),
+            number: text.parse().map_err(|_| ParseError::new(DAY, line, text, "invalid part number"))?,
         });
-        None
+        Ok(None)
     } else {
-        start
+        Ok(start)
     }
 }
 
-struct Symbol {
-    column: usize,
-    character: char,
-    gears: Vec<usize>,
+#[derive(Debug, PartialEq)]
+pub struct Symbol {
+    pub column: usize,
+    pub character: char,
 }
 
 impl Symbol {
-    fn new(index: usize, character: char) -> Symbol {
+    pub fn new(index: usize, character: char) -> Symbol {
         Symbol {
             column: index,
             character,
-            gears: Vec::<usize>::new(),
         }
     }
 }
 
-fn symbol_locations(line: &str) -> Vec<Symbol> {
+pub fn symbol_locations(line: &str) -> Result<Vec<Symbol>, ParseError> {
+    check_ascii(line)?;
     let mut symbol_locations = Vec::<Symbol>::new();
 
-    for (index, character) in line.chars().enumerate() {
-        if !character.is_numeric() && character != '.' {
+    for (index, character) in line.char_indices() {
+        if !character.is_ascii_digit() && character != '.' {
             symbol_locations.push(Symbol::new(index, character));
         }
     }
-    symbol_locations
+    Ok(symbol_locations)
 }
 
 #[cfg(test)]
@@ -127,7 +252,7 @@ mod tests {
 
     #[test]
     fn test_symbol_position() -> Result<(), &'static str> {
-        let result = symbol_locations("...*......");
+        let result = symbol_locations("...*......").unwrap();
 
         assert_eq!(result[0].column, 3);
 
@@ -136,7 +261,7 @@ mod tests {
 
     #[test]
     fn test_number_groups_start() -> Result<(), &'static str> {
-        let result = number_groups("123......");
+        let result = number_groups("123......").unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].start, 0);
         assert_eq!(result[0].end, 2);
@@ -147,7 +272,7 @@ mod tests {
 
     #[test]
     fn test_number_groups_inner() -> Result<(), &'static str> {
-        let result = number_groups("....321..");
+        let result = number_groups("....321..").unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].start, 4);
         assert_eq!(result[0].end, 6);
@@ -158,7 +283,7 @@ mod tests {
 
     #[test]
     fn test_number_groups_end() -> Result<(), &'static str> {
-        let result = number_groups("......99");
+        let result = number_groups("......99").unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].start, 6);
         assert_eq!(result[0].end, 7);
@@ -169,7 +294,7 @@ mod tests {
 
     #[test]
     fn test_number_groups_multiple() -> Result<(), &'static str> {
-        let result = number_groups("11.22");
+        let result = number_groups("11.22").unwrap();
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].start, 0);
         assert_eq!(result[0].end, 1);
@@ -181,69 +306,77 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_number_groups_overflow() -> Result<(), &'static str> {
+        let error = Schematic::parse("..1..\n..123456789012345678901234567890*").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.text, "123456789012345678901234567890");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gear_ratio_overflow() -> Result<(), &'static str> {
+        let schematic = Schematic::parse("4294967296*4294967296").unwrap();
+        assert_eq!(sum_of_gear_ratios(&schematic), Err(SolveError::overflow(DAY, "the ratio of the gear at row 1, column 11 overflows")));
+
+        let schematic = Schematic::parse("4294967295*4294967295\n\n4294967295*4294967295").unwrap();
+        assert_eq!(sum_of_gear_ratios(&schematic), Err(SolveError::overflow(DAY, "the sum of the gear ratios overflows")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_ascii_lines() -> Result<(), &'static str> {
+        let error = Schematic::parse("..1..\n.é12..").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "é"));
+
+        let error = number_groups("½..").unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (1, "unexpected non-ASCII character"));
+        assert!(symbol_locations("..*.½").is_err_and(|error| error.column == 5));
+
+        Ok(())
+    }
+
     #[test]
     fn test_has_symbol_in_range() -> Result<(), &'static str> {
         fn symb(column: usize) -> Vec<Symbol> {
             vec![Symbol::new(column, '+')]
         }
 
-        assert!(has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &mut symb(0)));
-        assert!(has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &mut symb(1)));
-        assert!(has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &mut symb(2)));
-        assert!(has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &mut symb(3)));
-        assert!(!has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &mut symb(5)));
+        assert!(has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &symb(0)));
+        assert!(has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &symb(1)));
+        assert!(has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &symb(2)));
+        assert!(has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &symb(3)));
+        assert!(!has_symbol_in_range(&NumberGroup { start: 0, end: 2, number: 123 }, &symb(5)));
 
-        assert!(!has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &mut symb(3)));
-        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &mut symb(4)));
-        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &mut symb(5)));
-        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &mut symb(6)));
-        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &mut symb(7)));
-        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &mut symb(8)));
-        assert!(!has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &mut symb(9)));
+        assert!(!has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &symb(3)));
+        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &symb(4)));
+        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &symb(5)));
+        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &symb(6)));
+        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &symb(7)));
+        assert!(has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &symb(8)));
+        assert!(!has_symbol_in_range(&NumberGroup { start: 5, end: 7, number: 123 }, &symb(9)));
 
         Ok(())
     }
 
     #[test]
     fn test_example() -> Result<(), &'static str> {
-        let lines = [
-            "467..114..",
-            "...*......",
-            "..35..633.",
-            "......#...",
-            "617*......",
-            ".....+.58.",
-            "..592.....",
-            "......755.",
-            "...$.*....",
-            ".664.598.."].to_vec();
-
-        assert_eq!(analyze(lines, 1), 4361);
+        let schematic = Schematic::parse(EXAMPLES[0].input).unwrap();
+
+        assert_eq!(sum_of_part_numbers(&schematic), 4361);
 
         Ok(())
     }
 
     #[test]
     fn test_example2() -> Result<(), &'static str> {
-        let lines = [
-            "467..114..",
-            "...*......",
-            "..35..633.",
-            "......#...",
-            "617*......",
-            ".....+.58.",
-            "..592.....",
-            "......755.",
-            "...$.*....",
-            ".664.598.."].to_vec();
-
-        assert_eq!(analyze(lines, 2), 467835);
+        let schematic = Schematic::parse(EXAMPLES[0].input).unwrap();
+
+        assert_eq!(sum_of_gear_ratios(&schematic), Ok(467835));
 
         Ok(())
     }
 }
-
-pub(crate) fn run() {
-    puzzle(1);
-    puzzle(2);
-}
@@ -1,78 +1,167 @@
 use std::collections::HashSet;
-use std::fs::read_to_string;
 use std::str::Split;
 
-fn puzzle(part: i32) {
-    let string = read_to_string("./04_data.txt").unwrap();
-    let lines = string.lines().collect::<Vec<_>>();
+use crate::error::{ParseError, SolveError};
+use crate::generate::Rng;
+use crate::solution::{Answer, Contribution, Example, Part, Solution};
 
-    let sum = analyze(lines, part);
-    println!("day 4 part {}: {}", part, sum)
-}
+const DAY: u8 = 4;
 
-fn analyze(lines: Vec<&str>, part: i32) -> usize {
-    let mut sum: usize = 0;
-    let mut copies: Vec<(usize, usize)> = Vec::new();
-
-    for (_, line) in lines.iter().enumerate() {
-        let (win, copy) = parse_line(line);
-        if part == 1 {
-            sum += win;
-        } else {
-            let number_of_copies = fetch_copies(&mut copies);
-            let mut instances = number_of_copies;
-            instances += 1;
-            if copy > 0 {
-                copies.push((copy, 1));
-            }
-            copies.push((copy, number_of_copies));
-            sum += instances;
-        }
+pub struct Day04;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#,
+        part1: Some(13),
+        part2: Some(30),
+    },
+];
+
+impl Solution for Day04 {
+    type Model = Vec<Card>;
+
+    fn day(&self) -> u8 {
+        DAY
     }
 
-    sum
-}
+    fn title(&self) -> &'static str {
+        "Scratchcards"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Card>, ParseError> {
+        parse_cards(input)
+    }
+
+    fn part1(&self, cards: &Vec<Card>) -> Result<Answer, SolveError> {
+        Ok(total_points(cards)? as Answer)
+    }
 
-fn fetch_copies(copies_store: &mut Vec<(usize,usize)>) -> usize {
-    if copies_store.len() == 0 {
-        return 0;
+    fn part2(&self, cards: &Vec<Card>) -> Result<Answer, SolveError> {
+        Ok(total_cards(cards)? as Answer)
     }
-    let mut copies = 0;
-    let mut index: usize = 0;
-    loop {
-        if copies_store[index].0 > 0 {
-            copies += copies_store[index].1;
-            copies_store[index] = (copies_store[index].0 - 1 , copies_store[index].1);
+
+    // only explained once the part is solved, so the points and instances fit
+    fn explain(&self, cards: &Vec<Card>, part: Part) -> Vec<Contribution> {
+        match part {
+            Part::One => cards.iter().enumerate()
+                .map(|(index, card)| Contribution::new(format!("card {}: {} matches", index + 1, card.matches()), card.points().unwrap_or_default()))
+                .collect(),
+            Part::Two => card_instances(cards).unwrap_or_default().into_iter().enumerate()
+                .map(|(index, instances)| {
+                    let copies = instances - 1;
+                    Contribution::new(format!("card {}: {} won cop{}", index + 1, copies, if copies == 1 { "y" } else { "ies" }), instances)
+                })
+                .collect(),
         }
-        if copies_store[index].0 == 0 {
-            copies_store.remove(index);
-        } else {
-            index += 1;
+    }
+
+    fn evaluate_line(&self, line: &str, part: Part) -> Result<String, SolveError> {
+        let card = Card::parse(line)?;
+        Ok(match part {
+            Part::One => format!("{} matches, {} points", card.matches(), card_points(1, &card)?),
+            Part::Two => format!("{} matches, wins a copy of each of the next {} cards", card.matches(), card.matches()),
+        })
+    }
+
+    // few numbers per card keep the average matches below one, otherwise the number of copies grows exponentially
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for id in 1..=size {
+            let mut numbers = |count: usize| {
+                let mut pool = (1..=99).collect::<Vec<usize>>();
+                rng.shuffle(&mut pool);
+                pool[..count].iter().map(|number| format!("{:>2}", number)).collect::<Vec<_>>().join(" ")
+            };
+            let winning_numbers = numbers(5);
+            let numbers = numbers(8);
+            input.push_str(&format!("Card {:>3}: {} | {}\n", id, winning_numbers, numbers));
         }
-        if index >= copies_store.len() {
-            break;
+        Some(input)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Card {
+    pub winning_numbers: HashSet<usize>,
+    pub numbers: HashSet<usize>,
+}
+
+impl Card {
+    pub fn parse(line: &str) -> Result<Card, ParseError> {
+        let (_card, numbers) = line.trim().split_once(':')
+            .ok_or_else(|| ParseError::new(DAY, line, line, "expected 'Card <id>: <winning numbers> | <numbers>'"))?;
+        let mut split2 = numbers.trim().split('|');
+
+        let winning_numbers = HashSet::from_iter(extract_numbers(line, &mut split2)?);
+        let numbers = HashSet::from_iter(extract_numbers(line, &mut split2)?);
+        Ok(Card { winning_numbers, numbers })
+    }
+
+    pub fn matches(&self) -> usize {
+        self.numbers.intersection(&self.winning_numbers).count()
+    }
+
+    // `None` if the points do not fit
+    pub fn points(&self) -> Option<usize> {
+        match self.matches() {
+            0 => Some(0),
+            matches => 1usize.checked_shl(u32::try_from(matches - 1).ok()?),
         }
     }
+}
 
-    copies
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    input.lines().enumerate()
+        .map(|(line_index, line)| Card::parse(line).map_err(|error| error.at_line(line_index + 1)))
+        .collect()
 }
 
-fn parse_line(line: &str) -> (usize, usize) {
-    let mut split1 = line.trim().split(':');
-    let _card = split1.next().unwrap().trim();
-    let mut split2 = split1.next().unwrap().trim().split('|');
+pub fn total_points(cards: &[Card]) -> Result<usize, SolveError> {
+    cards.iter().enumerate().try_fold(0usize, |sum, (index, card)| {
+        sum.checked_add(card_points(index + 1, card)?).ok_or_else(|| SolveError::overflow(DAY, "the sum of the points overflows"))
+    })
+}
 
-    let winning_numbers: HashSet<usize> = HashSet::from_iter(extract_numbers(&mut split2));
-    let numbers = HashSet::from_iter(extract_numbers(&mut split2));
+fn card_points(number: usize, card: &Card) -> Result<usize, SolveError> {
+    card.points().ok_or_else(|| SolveError::overflow(DAY, &format!("the points of card {} overflow", number)))
+}
 
-    let copy = numbers.intersection(&winning_numbers).count();
+// every card wins one copy of each of the next `matches` cards, for every instance of it
+pub fn total_cards(cards: &[Card]) -> Result<usize, SolveError> {
+    card_instances(cards)?.iter().try_fold(0usize, |sum, instances| {
+        sum.checked_add(*instances).ok_or_else(|| SolveError::overflow(DAY, "the total number of cards overflows"))
+    })
+}
 
-    (1 << copy >> 1, copy)
+// how many instances of every card there are once all copies are won
+pub fn card_instances(cards: &[Card]) -> Result<Vec<usize>, SolveError> {
+    let mut instances = vec![1usize; cards.len()];
+    for (index, card) in cards.iter().enumerate() {
+        let end = (index + 1 + card.matches()).min(cards.len());
+        for next in index + 1..end {
+            instances[next] = instances[next].checked_add(instances[index])
+                .ok_or_else(|| SolveError::overflow(DAY, &format!("the instances of card {} overflow", next + 1)))?;
+        }
+    }
+    Ok(instances)
 }
 
-fn extract_numbers(split2: &mut Split<char>) -> Vec<usize> {
-    let part = split2.next().unwrap().trim();
-    part.split_whitespace().map(|number| number.parse().unwrap()).collect::<Vec<usize>>()
+fn extract_numbers(line: &str, split2: &mut Split<char>) -> Result<Vec<usize>, ParseError> {
+    let part = split2.next()
+        .ok_or_else(|| ParseError::new(DAY, line, &line[line.len()..], "missing '|' between winning numbers and numbers"))?
+        .trim();
+    part.split_whitespace()
+        .map(|number| number.parse().map_err(|_| ParseError::new(DAY, line, number, "invalid number")))
+        .collect::<Result<Vec<usize>, _>>()
 }
 
 #[cfg(test)]
@@ -81,30 +170,56 @@ mod tests {
 
     #[test]
     fn test_parse_line() -> Result<(), &'static str> {
-        let (win, copy) = parse_line("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let card = Card::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
 
-        assert_eq!(win, 8);
-        assert_eq!(copy, 4);
+        assert_eq!(card.points(), Some(8));
+        assert_eq!(card.matches(), 4);
 
         Ok(())
     }
 
     #[test]
     fn test_complete() {
-        let lines = [
-            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
-            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
-            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
-            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
-            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
-            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"].to_vec();
+        let cards = parse_cards(EXAMPLES[0].input).unwrap();
 
-        assert_eq!(analyze(lines.clone(), 1), 13);
-        assert_eq!(analyze(lines, 2), 30);
+        assert_eq!(total_points(&cards), Ok(13));
+        assert_eq!(total_cards(&cards), Ok(30));
     }
-}
 
-pub(crate) fn run() {
-    puzzle(1);
-    puzzle(2);
+    // a card matching the numbers 1 to `matches`
+    fn card_with_matches(matches: usize) -> Card {
+        let numbers = (1..=matches).map(|number| number.to_string()).collect::<Vec<_>>().join(" ");
+        Card::parse(&format!("Card 1: {} | {}", numbers, numbers)).unwrap()
+    }
+
+    #[test]
+    fn test_points_overflow() {
+        assert_eq!(card_with_matches(64).points(), Some(1 << 63));
+        assert_eq!(card_with_matches(65).points(), None);
+        assert_eq!(total_points(&[card_with_matches(1), card_with_matches(65)]), Err(SolveError::overflow(DAY, "the points of card 2 overflow")));
+        assert_eq!(total_points(&[card_with_matches(64), card_with_matches(64)]), Err(SolveError::overflow(DAY, "the sum of the points overflows")));
+    }
+
+    #[test]
+    fn test_instances_overflow() {
+        // every card wins a copy of all later cards, which doubles the instances from card to card
+        let cards = (0..65).map(|index| card_with_matches(64 - index)).collect::<Vec<_>>();
+        assert_eq!(card_instances(&cards), Err(SolveError::overflow(DAY, "the instances of card 65 overflow")));
+
+        // the 64 doubling cards add up to 2^64 - 1 instances, one more card does not fit
+        let cards = (0..65).map(|index| card_with_matches(63usize.saturating_sub(index))).collect::<Vec<_>>();
+        assert_eq!(card_instances(&cards).unwrap()[63], 1 << 63);
+        assert_eq!(total_cards(&cards), Err(SolveError::overflow(DAY, "the total number of cards overflows")));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_cards("Card 1: 41 | 83\nCard 2: 41 4x | 83").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 12, "4x"));
+
+        let error = Card::parse("Card 1: 41 83").unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (14, "missing '|' between winning numbers and numbers"));
+
+        assert!(Card::parse("Card 1 41 | 83").is_err());
+    }
 }
@@ -1,24 +1,160 @@
-use std::fs::read_to_string;
+use crate::error::{ParseError, SolveError};
+use crate::event;
+use crate::generate::Rng;
+use crate::solution::{Answer, Example, Solution};
+use crate::trace::Level;
 
-fn puzzle(part: i32) {
-    let string = read_to_string("./05_data.txt").unwrap();
-    let lines = string.lines().collect::<Vec<_>>();
+const DAY: u8 = 5;
 
-    let sum = analyze(lines, part);
-    println!("day 5 part {}: {}", part, sum)
+pub struct Day05;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: r#"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+"#,
+        part1: Some(35),
+        part2: Some(46),
+    },
+];
+
+impl Solution for Day05 {
+    type Model = Almanac;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "If You Give A Seed A Fertilizer"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Almanac, ParseError> {
+        parse_almanac(input)
+    }
+
+    fn part1(&self, almanac: &Almanac) -> Result<Answer, SolveError> {
+        Ok(lowest_location(almanac)? as Answer)
+    }
+
+    fn part2(&self, almanac: &Almanac) -> Result<Answer, SolveError> {
+        Ok(lowest_location_of_ranges(almanac)? as Answer)
+    }
+
+    // `size` seed ranges, and maps with up to `size` ranges each
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let seeds = (0..size).flat_map(|_| {
+            let start = rng.below(LIMIT);
+            [start, rng.range(1..=(LIMIT - start).min(LIMIT / 16))]
+        }).map(|number| number.to_string()).collect::<Vec<_>>();
+
+        let mut input = format!("seeds: {}\n", seeds.join(" "));
+        for name in MAP_NAMES {
+            input.push_str(&format!("\n{} map:\n", name));
+            for range_map in generate_map(rng, size) {
+                input.push_str(&format!("{} {} {}\n", range_map.target, range_map.range.start, range_map.range.size));
+            }
+        }
+        Some(input)
+    }
 }
 
-fn analyze(lines: Vec<&str>, part: i32) -> usize {
+// the numbers of generated almanacs stay below 2^32 like the puzzle's
+const LIMIT: usize = 1 << 32;
+
+const MAP_NAMES: [&str; 7] = [
+    "seed-to-soil", "soil-to-fertilizer", "fertilizer-to-water", "water-to-light", "light-to-temperature", "temperature-to-humidity", "humidity-to-location",
+];
+
+// ranges with gaps between their sources, moved to a random order of non overlapping targets
+fn generate_map(rng: &mut Rng, size: usize) -> Vec<RangeMap> {
+    let count = rng.range(1..=size.max(1));
+    let mut bounds = (0..2 * count).map(|_| rng.below(LIMIT)).collect::<Vec<_>>();
+    bounds.sort();
+    bounds.dedup();
+    let mut sources = bounds.chunks_exact(2).map(|pair| Range::new(pair[0], pair[1] - pair[0])).collect::<Vec<_>>();
+    rng.shuffle(&mut sources);
+
+    let total = sources.iter().map(|range| range.size).sum::<usize>();
+    let mut target = rng.below(LIMIT - total + 1);
+    sources.into_iter().map(|source| {
+        let range_map = RangeMap::new(source.start, source.size, target);
+        target += source.size;
+        range_map
+    }).collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Almanac {
+    pub seeds: Vec<usize>,
+    // the seeds read as pairs of range start and length, an odd number of seeds only fails part 2
+    pub seed_ranges: Result<Vec<Range>, ParseError>,
+    pub map_chain: Vec<Vec<RangeMap>>,
+}
+
+pub fn parse_almanac(input: &str) -> Result<Almanac, ParseError> {
     let mut seeds: Vec::<usize> = Vec::new();
     let mut map_chain = Vec::<Vec<RangeMap>>::new();
     let mut map = Vec::<RangeMap>::new();
+    let mut seed_ranges = Ok(Vec::new());
 
-    for (line_index, line) in lines.iter().enumerate() {
+    if input.lines().next().is_none() {
+        return Err(ParseError::new(DAY, input, input, "missing seeds").at_line(1));
+    }
+    for (line_index, line) in input.lines().enumerate() {
         if line_index == 0 {
-            parse_seeds(line, &mut seeds);
+            parse_seeds(line, &mut seeds).map_err(|error| error.at_line(1))?;
+            // part 1 reads single seeds, so malformed ranges only fail part 2
+            let empty_range = seeds.chunks(2).position(|pair| pair.get(1) == Some(&0));
+            let unbounded_range = seeds.chunks(2).position(|pair| pair.get(1).is_some_and(|size| pair[0].checked_add(*size).is_none()));
+            let length = |index: usize| line.split_whitespace().nth(2 + 2 * index).unwrap_or(line);
+            seed_ranges = if !seeds.len().is_multiple_of(2) {
+                Err(ParseError::new(DAY, line, line, "expected pairs of seed range start and length").at_line(1))
+            } else if let Some(index) = empty_range {
+                Err(ParseError::new(DAY, line, length(index), "expected a range length above 0").at_line(1))
+            } else if let Some(index) = unbounded_range {
+                Err(ParseError::new(DAY, line, length(index), "range exceeds the largest number").at_line(1))
+            } else {
+                Ok(get_seed_ranges(&seeds))
+            };
             continue;
         }
-        if line.trim().len() == 0 {
+        if line.trim().is_empty() {
             if line_index > 1 {
                 map_chain.push(map);
                 map = Vec::new();
@@ -28,37 +164,42 @@ fn analyze(lines: Vec<&str>, part: i32) -> usize {
         if line.ends_with("map:") {
             continue;
         }
-        parse_map(&mut map, line);
+        parse_map(&mut map, line).map_err(|error| error.at_line(line_index + 1))?;
     }
     map_chain.push(map);
 
-    let mut min = usize::MAX;
-    if part == 2 {
-        let seed_ranges = get_seed_ranges(&mut seeds);
-        for seed_range in seed_ranges {
-            let value = apply_map(&seed_range, &map_chain);
-            if value < min {
-                min = value;
-            }
-        }
-    } else {
-        for seed in seeds {
-            let value = map_value(&map_chain, seed);
-            if value < min {
-                min = value;
-            }
-        }
-    }
+    Ok(Almanac { seeds, seed_ranges, map_chain })
+}
+
+// an error without seeds, which a parsed almanac always has
+pub fn lowest_location(almanac: &Almanac) -> Result<usize, ParseError> {
+    almanac.seeds.iter().map(|seed| {
+        let location = map_value(&almanac.map_chain, *seed);
+        event!(Level::Debug, DAY, "seed", seed = seed, location = location);
+        location
+    }).min().ok_or_else(no_seeds)
+}
 
-    min
+pub fn lowest_location_of_ranges(almanac: &Almanac) -> Result<usize, ParseError> {
+    let seed_ranges = almanac.seed_ranges.as_ref().map_err(Clone::clone)?;
+    seed_ranges.iter().map(|seed_range| {
+        let location = apply_map(seed_range, &almanac.map_chain);
+        event!(Level::Debug, DAY, "seed_range", start = seed_range.start, size = seed_range.size, location = location);
+        location
+    }).min().ok_or_else(no_seeds)
 }
 
-fn apply_map(seed_range: &Range, map_chain: &Vec<Vec<RangeMap>>) -> usize {
+fn no_seeds() -> ParseError {
+    ParseError::new(DAY, "", "", "expected at least one seed").at_line(1)
+}
+
+pub fn apply_map(seed_range: &Range, map_chain: &[Vec<RangeMap>]) -> usize {
     let mut unmapped_range_queue = vec![seed_range.clone()];
     let mut mapped_range_queue = Vec::<Range>::new();
 
-    for range_maps in map_chain {
+    for (stage, range_maps) in map_chain.iter().enumerate() {
         unmapped_range_queue.append(&mut mapped_range_queue);
+        event!(Level::Trace, DAY, "ranges", seed = seed_range.start, stage = stage, ranges = normalize(unmapped_range_queue.clone()));
         mapped_range_queue = Vec::<Range>::new();
 
         for range_map in range_maps {
@@ -78,21 +219,11 @@ fn apply_map(seed_range: &Range, map_chain: &Vec<Vec<RangeMap>>) -> usize {
     range_queue[0].start
 }
 
-fn get_seed_ranges(seeds: &mut Vec<usize>) -> Vec<Range> {
-    let mut iter = seeds.iter();
-    let mut ranges = Vec::<Range>::new();
-    loop {
-        let base = iter.next().unwrap();
-        let size = iter.next().unwrap();
-        ranges.push(Range::new(*base, *size));
-        if iter.len() == 0 {
-            break;
-        }
-    }
-    ranges
+pub fn get_seed_ranges(seeds: &[usize]) -> Vec<Range> {
+    seeds.chunks_exact(2).map(|pair| Range::new(pair[0], pair[1])).collect()
 }
 
-fn map_value(map_chain: &Vec<Vec<RangeMap>>, seed: usize) -> usize {
+pub fn map_value(map_chain: &Vec<Vec<RangeMap>>, seed: usize) -> usize {
     let mut value = seed;
     for maps in map_chain {
         for map in maps {
@@ -105,51 +236,67 @@ fn map_value(map_chain: &Vec<Vec<RangeMap>>, seed: usize) -> usize {
     value
 }
 
-fn parse_seeds(line: &str, seeds: &mut Vec<usize>) {
+pub fn parse_seeds(line: &str, seeds: &mut Vec<usize>) -> Result<(), ParseError> {
     let elements = line.split_whitespace().collect::<Vec<_>>();
-    if elements.len() > 2 {
-        seeds.append(&mut parse_numbers(&elements[1..]));
-    } else {
-        println!("seeds empty: {:?} from {}", elements, line);
+    if elements.first() != Some(&"seeds:") {
+        return Err(ParseError::new(DAY, line, elements.first().unwrap_or(&line), "expected 'seeds:'"));
+    }
+    if elements.len() < 2 {
+        return Err(ParseError::new(DAY, line, &line[line.len()..], "expected at least one seed"));
     }
+    seeds.append(&mut parse_numbers(line, &elements[1..])?);
+    Ok(())
 }
 
-fn parse_map(map: &mut Vec::<RangeMap>, line: &str) {
-    let elements = parse_numbers(&line.split_whitespace().collect::<Vec<_>>());
+pub fn parse_map(map: &mut Vec::<RangeMap>, line: &str) -> Result<(), ParseError> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let elements = parse_numbers(line, &words)?;
+    if elements.len() != 3 {
+        return Err(ParseError::new(DAY, line, line, "expected destination start, source start and length"));
+    }
     let (target, source, size) = (elements[0], elements[1], elements[2]);
+    if size == 0 {
+        return Err(ParseError::new(DAY, line, words[2], "expected a range length above 0"));
+    }
+    // the ranges end before the largest number, so that their ends and mapped values fit
+    if source.checked_add(size).is_none() || target.checked_add(size).is_none() {
+        return Err(ParseError::new(DAY, line, words[2], "range exceeds the largest number"));
+    }
     map.push(RangeMap::new(source, size, target));
+    Ok(())
 }
 
-fn parse_numbers(line: &[&str]) -> Vec<usize> {
-    line.iter().map(|element| { element.parse::<usize>().unwrap() }).collect::<Vec<_>>()
+fn parse_numbers(line: &str, elements: &[&str]) -> Result<Vec<usize>, ParseError> {
+    elements.iter().map(|element| {
+        element.parse::<usize>().map_err(|_| ParseError::new(DAY, line, element, "invalid number"))
+    }).collect::<Result<Vec<_>, _>>()
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Range {
-    start: usize,
-    size: usize,
+pub struct Range {
+    pub start: usize,
+    pub size: usize,
 }
 
 impl Range {
-    fn new(start: usize, size: usize) -> Range {
+    pub fn new(start: usize, size: usize) -> Range {
         Range { start, size }
     }
 
-    fn end(&self) -> usize {
+    pub fn end(&self) -> usize {
         self.start + self.size - 1
     }
 
-    fn contains(&self, value: &usize) -> bool {
-        self.start <= *value && *value < self.start + self.size
+    pub fn contains(&self, value: &usize) -> bool {
+        self.start <= *value && *value - self.start < self.size
     }
 
     pub fn overlaps(&self, other: &Range) -> bool {
-        self.contains_edges(&other) | other.contains_edges(self)
+        self.contains_edges(other) | other.contains_edges(self)
     }
 
     fn contains_edges(&self, other: &Range) -> bool {
-        let result = self.contains(&other.start) | self.contains(&(other.end()));
-        result
+        self.contains(&other.start) | self.contains(&(other.end()))
     }
 
     pub fn offset(&self, value: &usize) -> Option<usize> {
@@ -162,13 +309,13 @@ impl Range {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct RangeMap {
-    range: Range,
-    target: usize,
+pub struct RangeMap {
+    pub range: Range,
+    pub target: usize,
 }
 
 impl RangeMap {
-    fn new(source: usize, size: usize, target: usize) -> RangeMap {
+    pub fn new(source: usize, size: usize, target: usize) -> RangeMap {
         RangeMap { range: Range::new(source, size), target }
     }
 
@@ -185,7 +332,7 @@ impl RangeMap {
         if self.range.overlaps(range) {
             if !self.range.contains(&range.start) && self.contains(&range.end()) {
                 // start outside, end inside
-                let difference = &range.end() - self.range.start + 1;
+                let difference = range.end() - self.range.start + 1;
                 (vec![
                     Range::new(range.start, range.size - difference),
                 ], vec![
@@ -201,7 +348,7 @@ impl RangeMap {
                 ])
             } else if !self.range.contains(&range.start) && !self.contains(&range.end()) {
                 // start outside, end outside
-                let difference2 = &range.end() - self.range.start + 1;
+                let difference2 = range.end() - self.range.start + 1;
                 (vec![
                     Range::new(range.start, self.range.start - range.start),
                     Range::new(self.range.end() + 1, range.size - difference2),
@@ -221,11 +368,11 @@ impl RangeMap {
     }
 }
 
-fn normalize(ranges: Vec::<Range>) -> Vec::<Range> {
+pub fn normalize(ranges: Vec::<Range>) -> Vec::<Range> {
     let mut ranges = ranges;
-    ranges.sort_by(|a, b| a.start.cmp(&b.start));
+    ranges.sort_by_key(|a| a.start);
 
-    if ranges.len() == 0 {
+    if ranges.is_empty() {
         return vec![];
     }
 
@@ -244,7 +391,7 @@ fn normalize(ranges: Vec::<Range>) -> Vec::<Range> {
     }
     updated.push(current);
 
-    return updated;
+    updated
 }
 
 #[cfg(test)]
@@ -291,6 +438,29 @@ mod tests {
         assert_eq!(map_range.get(&4), 9);
     }
 
+    #[test]
+    fn test_range_at_largest_number() {
+        let range = Range::new(usize::MAX - 1, 1);
+        assert!(range.contains(&(usize::MAX - 1)));
+        assert!(!range.contains(&usize::MAX));
+        assert_eq!(range.end(), usize::MAX - 1);
+        assert_eq!(RangeMap::new(0, 2, usize::MAX - 2).get(&1), usize::MAX - 1);
+    }
+
+    #[test]
+    fn test_unbounded_ranges() {
+        let error = parse_almanac("seeds: 79 14\n\nseed-to-soil map:\n0 18446744073709551615 1").unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (4, 24, "range exceeds the largest number"));
+        let error = parse_almanac("seeds: 79 14\n\nseed-to-soil map:\n18446744073709551615 0 1").unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (4, 24, "range exceeds the largest number"));
+        assert!(parse_almanac("seeds: 79 14\n\nseed-to-soil map:\n18446744073709551614 18446744073709551614 1").is_ok());
+
+        let almanac = parse_almanac("seeds: 18446744073709551615 1").unwrap();
+        assert_eq!(lowest_location(&almanac), Ok(1));
+        let error = lowest_location_of_ranges(&almanac).unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (1, 29, "range exceeds the largest number"));
+    }
+
     #[test]
     fn test_range_overlaps() {
         let mut other = Range::new(0, 2);
@@ -389,17 +559,21 @@ mod tests {
     #[test]
     fn test_parse_seeds() -> Result<(), &'static str> {
         let mut seeds = Vec::new();
-        parse_seeds("seeds: 2 5 7", &mut seeds);
+        parse_seeds("seeds: 2 5 7", &mut seeds).unwrap();
 
         assert_eq!(seeds, vec![2, 5, 7]);
 
+        let mut seeds = Vec::new();
+        parse_seeds("seeds: 5", &mut seeds).unwrap();
+        assert_eq!(seeds, vec![5]);
+
         Ok(())
     }
 
     #[test]
     fn test_parse_map() -> Result<(), &'static str> {
         let mut map = Vec::<RangeMap>::new();
-        parse_map(&mut map, "50 98 2");
+        parse_map(&mut map, "50 98 2").unwrap();
 
         assert_eq!(map, Vec::from([
             RangeMap::new(98, 2, 50),
@@ -410,48 +584,54 @@ mod tests {
 
     #[test]
     fn test_complete() {
-        let input = r#"seeds: 79 14 55 13
+        let almanac = parse_almanac(EXAMPLES[0].input).unwrap();
 
-seed-to-soil map:
-50 98 2
-52 50 48
+        assert_eq!(lowest_location(&almanac), Ok(35));
+        assert_eq!(lowest_location_of_ranges(&almanac), Ok(46));
+    }
 
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_almanac("seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2\n52 5O 48").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (5, 4, "5O"));
 
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
+        let error = parse_almanac("seeds: 79 14 55\n\nseed-to-soil map:\n50 98").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (4, "expected destination start, source start and length"));
 
-water-to-light map:
-88 18 7
-18 25 70
+        let almanac = parse_almanac("seeds: 79 14 55").unwrap();
+        assert_eq!(lowest_location(&almanac), Ok(14));
+        let error = lowest_location_of_ranges(&almanac).unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (1, "expected pairs of seed range start and length"));
 
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
+        assert_eq!(parse_seeds("plants: 1 2", &mut Vec::new()).unwrap_err().text, "plants:");
+    }
 
-temperature-to-humidity map:
-0 69 1
-1 0 69
+    #[test]
+    fn test_missing_seeds() {
+        let almanac = parse_almanac("seeds: 5\n\nseed-to-soil map:\n50 0 10").unwrap();
+        assert_eq!(almanac.seeds, vec![5]);
+        assert_eq!(lowest_location(&almanac), Ok(55));
+        assert_eq!(lowest_location_of_ranges(&almanac).unwrap_err().message, "expected pairs of seed range start and length");
 
-humidity-to-location map:
-60 56 37
-56 93 4
-"#;
-        let lines = input.lines().collect::<Vec<_>>();
+        let error = parse_almanac("seeds:\n\nseed-to-soil map:\n50 0 10").unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (1, 7, "expected at least one seed"));
+        let error = parse_almanac("").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (1, "missing seeds"));
 
-        assert_eq!(analyze(lines.clone(), 1), 35);
-        assert_eq!(analyze(lines, 2), 46);
+        let almanac = Almanac { seeds: vec![], seed_ranges: Ok(vec![]), map_chain: vec![] };
+        assert_eq!(lowest_location(&almanac).unwrap_err().message, "expected at least one seed");
+        assert_eq!(lowest_location_of_ranges(&almanac).unwrap_err().message, "expected at least one seed");
     }
-}
 
-pub(crate) fn run() {
-    puzzle(1);
-    puzzle(2);
+    #[test]
+    fn test_empty_ranges() {
+        let almanac = parse_almanac("seeds: 0 0").unwrap();
+        assert_eq!(lowest_location(&almanac), Ok(0));
+        let error = lowest_location_of_ranges(&almanac).unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (1, 10, "expected a range length above 0"));
+
+        let error = parse_almanac("seeds: 79 14 55 0\n\nseed-to-soil map:\n50 98 2\n52 50 0").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (5, 7, "0"));
+        assert_eq!(lowest_location_of_ranges(&parse_almanac("seeds: 79 14 55 0").unwrap()).unwrap_err().column, 17);
+    }
 }
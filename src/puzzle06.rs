@@ -1,62 +1,202 @@
-use std::fs::read_to_string;
 use std::slice::Iter;
 
-fn puzzle(part: i32) {
-    let string = read_to_string("./06_data.txt").unwrap();
-    let lines = string.lines().collect::<Vec<_>>();
+use crate::error::{ParseError, SolveError};
+use crate::generate::Rng;
+use crate::solution::{Answer, Example, Solution};
 
-    let sum = analyze(lines, part);
-    println!("day 6 part {}: {}", part, sum)
+const DAY: u8 = 6;
+
+pub struct Day06;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "Time:      7  15   30\nDistance:  9  40  200",
+        part1: Some(288),
+        part2: Some(71503),
+    },
+];
+
+impl Solution for Day06 {
+    type Model = Races;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Wait For It"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Races, ParseError> {
+        parse_races(input)
+    }
+
+    fn part1(&self, races: &Races) -> Result<Answer, SolveError> {
+        Ok(product_of_winning_counts(&races.races)? as Answer)
+    }
+
+    fn part2(&self, races: &Races) -> Result<Answer, SolveError> {
+        let race = races.single_race.clone()?;
+        let count = calculate_winning_solution_count(race.time, race.distance)
+            .ok_or_else(|| SolveError::overflow(DAY, "the square of the joined race time overflows"))?;
+        Ok(count as Answer)
+    }
+
+    // every race can be won, part 2 joins the races into a single one that only fits for a few, so there are at
+    // most `MAX_RACES` like in the puzzle whatever the size
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let races = (0..size.clamp(1, MAX_RACES)).map(|_| {
+            let time = rng.range(7..=99);
+            Race { time, distance: rng.below(time / 2 * (time - time / 2)) }
+        }).collect::<Vec<_>>();
+        let times = races.iter().map(|race| format!("{:>5}", race.time)).collect::<String>();
+        let distances = races.iter().map(|race| format!("{:>5}", race.distance)).collect::<String>();
+        Some(format!("Time:    {}\nDistance:{}\n", times, distances))
+    }
 }
 
-fn analyze(lines: Vec<&str>, part: i32) -> usize {
-    let mut iter = lines.iter();
-    let times = parse_numbers(&mut iter, part == 2);
-    let distances = parse_numbers(&mut iter, part == 2);
+const MAX_RACES: usize = 4;
 
-    let wins = times.iter().zip(distances.iter()).map(
-        |(time, record_distance)| {
-            calculate_winning_solution_count(*time, *record_distance)
-        }).collect::<Vec<usize>>();
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Race {
+    pub time: usize,
+    pub distance: usize,
+}
 
-    wins.iter().fold(1, |acc, win| acc * win)
+/// The races of the sheet, and the single race read when ignoring the whitespace between the numbers.
+#[derive(Debug, PartialEq)]
+pub struct Races {
+    pub races: Vec<Race>,
+    // an error if the joined numbers are too large, which only fails part 2
+    pub single_race: Result<Race, ParseError>,
 }
 
-fn parse_numbers(iter: &mut Iter<&str>, ignore_whitespace: bool) -> Vec<usize> {
-    let (_, data) = iter.next().unwrap().split_once(char::is_whitespace).unwrap();
-    let mut data = String::from(data);
-    if ignore_whitespace {
-        data = data.replace(" ", "");
+pub fn parse_races(input: &str) -> Result<Races, ParseError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut iter = lines.iter();
+    let (times, time) = parse_numbers(&mut iter).map_err(|error| error.at_line(1))?;
+    let (distances, distance) = parse_numbers(&mut iter).map_err(|error| error.at_line(2))?;
+    if times.len() != distances.len() {
+        return Err(ParseError::new(DAY, lines[1], lines[1], "expected one distance per time").at_line(2));
     }
-    data.split_whitespace().map(|element| { element.parse::<usize>().unwrap() }).collect::<Vec<_>>()
+
+    let races = times.iter().zip(distances.iter()).map(|(time, distance)| Race { time: *time, distance: *distance }).collect();
+    let single_race = time.map_err(|error| error.at_line(1))
+        .and_then(|time| Ok(Race { time, distance: distance.map_err(|error| error.at_line(2))? }));
+    Ok(Races { races, single_race })
+}
+
+pub fn product_of_winning_counts(races: &[Race]) -> Result<usize, SolveError> {
+    races.iter().enumerate().try_fold(1usize, |product, (index, race)| {
+        let count = calculate_winning_solution_count(race.time, race.distance)
+            .ok_or_else(|| SolveError::overflow(DAY, &format!("the square of the time of race {} overflows", index + 1)))?;
+        product.checked_mul(count).ok_or_else(|| SolveError::overflow(DAY, "the product of the winning counts overflows"))
+    })
 }
 
-fn calculate_winning_solution_count(time: usize, distance: usize) -> usize {
-    let offset_to_ensure_higher_win = 0.5;
+// returns the numbers of the line, and the single number they form when ignoring the whitespace
+fn parse_numbers(iter: &mut Iter<&str>) -> Result<(Vec<usize>, Result<usize, ParseError>), ParseError> {
+    let line = iter.next().ok_or_else(|| ParseError::new(DAY, "", "", "missing line"))?;
+    let (_, data) = line.split_once(char::is_whitespace)
+        .ok_or_else(|| ParseError::new(DAY, line, line, "expected a label followed by numbers"))?;
+    let numbers = data.split_whitespace()
+        .map(|element| element.parse::<usize>().map_err(|_| ParseError::new(DAY, line, element, "invalid number")))
+        .collect::<Result<Vec<_>, _>>()?;
+    let joined = data.replace(' ', "").parse::<usize>()
+        .map_err(|_| ParseError::new(DAY, line, data.trim(), "invalid number"));
+    Ok((numbers, joined))
+}
+
+// `None` if the square of the time does not fit
+pub fn calculate_winning_solution_count(time: usize, distance: usize) -> Option<usize> {
+    let square = time.checked_mul(time)?;
+    let wins = |hold: usize| hold * (time - hold) > distance;
+    if !wins(time / 2) {
+        return Some(0);
+    }
 
-    let base = time as f32 / 2.0;
-    let half = ((time * time) as f32 - 4.0 * (distance as f32 + offset_to_ensure_higher_win)).sqrt() / 2.0;
-    let lower = (base - half) as usize;
-    let upper = (base + half) as usize;
-    upper - lower
+    // the winning holds lie strictly between the roots of hold * (time - hold) = distance, the integer square root
+    // of the discriminant puts the lower bound within a step of the first winning hold
+    let mut lower = (time - (square - 4 * distance).isqrt()) / 2;
+    while !wins(lower) {
+        lower += 1;
+    }
+    while lower > 0 && wins(lower - 1) {
+        lower -= 1;
+    }
+    Some(time - 2 * lower + 1)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate::{self, DEFAULT_SEED, DEFAULT_SIZE};
 
     #[test]
     fn test_complete() {
-        let input = r#"Time:      7  15   30
-Distance:  9  40  200"#;
-        let lines = input.lines().collect::<Vec<_>>();
+        let races = parse_races(EXAMPLES[0].input).unwrap();
 
-        assert_eq!(analyze(lines.clone(), 1), 288);
-        assert_eq!(analyze(lines, 2), 71503);
+        assert_eq!(product_of_winning_counts(&races.races), Ok(288));
+        assert_eq!(races.single_race, Ok(Race { time: 71530, distance: 940200 }));
+        assert_eq!(Day06.part2(&races), Ok(71503));
     }
-}
 
-pub(crate) fn run() {
-    puzzle(1);
-    puzzle(2);
+    #[test]
+    fn test_exact_count() {
+        let brute_force = |time: usize, distance: usize| (0..=time).filter(|hold| hold * (time - hold) > distance).count();
+        for time in 0..60 {
+            for distance in 0..=time * time / 4 + 1 {
+                assert_eq!(calculate_winning_solution_count(time, distance), Some(brute_force(time, distance)), "time {} distance {}", time, distance);
+            }
+        }
+
+        let races = parse_races("Time:  7\nDistance:  9").unwrap();
+        assert_eq!(Day06.part2(&races), Ok(4));
+
+        // only the middle hold beats a record one below the best distance, which f32 cannot tell apart
+        assert_eq!(calculate_winning_solution_count(100_000_000, 2_499_999_999_999_999), Some(1));
+        assert_eq!(calculate_winning_solution_count(100_000_000, 2_500_000_000_000_000), Some(0));
+        assert_eq!(calculate_winning_solution_count(100_000_001, 2_500_000_049_999_999), Some(2));
+        assert_eq!(calculate_winning_solution_count(4_000_000_000, 1), Some(3_999_999_999));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let races = parse_races("Time:  5000000000  7  15   30\nDistance:  9  9  40  200").unwrap();
+        assert_eq!(calculate_winning_solution_count(races.races[0].time, 9), None);
+        assert_eq!(Day06.part1(&races), Err(SolveError::overflow(DAY, "the square of the time of race 1 overflows")));
+        assert_eq!(Day06.part2(&races), Err(SolveError::overflow(DAY, "the square of the joined race time overflows")));
+
+        let races = (0..5).map(|_| Race { time: 100_000, distance: 0 }).collect::<Vec<_>>();
+        assert_eq!(product_of_winning_counts(&races), Err(SolveError::overflow(DAY, "the product of the winning counts overflows")));
+    }
+
+    #[test]
+    fn test_generated_input_of_default_size() {
+        let input = generate::generate(&Day06, DEFAULT_SIZE, DEFAULT_SEED).unwrap();
+        let races = parse_races(&input).unwrap();
+        assert_eq!(races.races.len(), MAX_RACES);
+        assert!(Day06.part1(&races).is_ok());
+        assert!(Day06.part2(&races).is_ok());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_races("Time:      7  15   30\nDistance:  9  4O  200").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 15, "4O"));
+
+        let error = parse_races("Time:      7  15   30\nDistance:  9  40").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (2, "expected one distance per time"));
+
+        let error = parse_races("Time:      7  15   30").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (2, "missing line"));
+
+        let races = parse_races("Time:      1000000000  1000000000  1000000000\nDistance:  9  40  200").unwrap();
+        assert_eq!(races.races.len(), 3);
+        assert_eq!(races.single_race.unwrap_err().line, 1);
+    }
 }
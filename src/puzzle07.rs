@@ -1,51 +1,131 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::fs::read_to_string;
 
-fn puzzle(part: i32) {
-    let string = read_to_string("./07_data.txt").unwrap();
-    let lines = string.lines().collect::<Vec<_>>();
+use crate::error::{ParseError, SolveError};
+use crate::event;
+use crate::generate::Rng;
+use crate::solution::{Answer, Contribution, Example, Part, Solution};
+use crate::trace::Level;
 
-    let sum = analyze(lines, part);
-    println!("day 6 part {}: {}", part, sum)
+const DAY: u8 = 7;
+
+pub struct Day07;
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483",
+        part1: Some(6440),
+        part2: Some(5905),
+    },
+];
+
+impl Solution for Day07 {
+    type Model = Vec<Bid>;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Camel Cards"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Bid>, ParseError> {
+        parse_bids(input)
+    }
+
+    fn part1(&self, bids: &Vec<Bid>) -> Result<Answer, SolveError> {
+        Ok(total_winnings(bids, false)? as Answer)
+    }
+
+    // jokers count as the card making the strongest hand
+    fn part2(&self, bids: &Vec<Bid>) -> Result<Answer, SolveError> {
+        Ok(total_winnings(bids, true)? as Answer)
+    }
+
+    // only explained once the part is solved, so the winnings fit
+    fn explain(&self, bids: &Vec<Bid>, part: Part) -> Vec<Contribution> {
+        ranked_bids(bids, part == Part::Two).into_iter().map(|(rank, bid)| {
+            Contribution::new(format!("hand {} bid {} rank {}", bid.hand.hand, bid.bid, rank), rank.checked_mul(bid.bid).unwrap_or_default())
+        }).collect()
+    }
+
+    // a line is a hand, optionally followed by its bid
+    fn evaluate_line(&self, line: &str, part: Part) -> Result<String, SolveError> {
+        let cards = line.split_whitespace().next().ok_or_else(|| ParseError::new(DAY, line, line, "missing hand"))?;
+        let hand = Hand::parse(cards).map_err(|error| error.within(line, cards))?;
+        let rank = hand.rank(part == Part::Two);
+        Ok(format!("{} (strength {})", HAND_TYPES[rank], rank))
+    }
+
+    // the cards of a hand are drawn from a few values, so that all hand types occur
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let cards = CARDS.chars().collect::<Vec<_>>();
+        let mut input = String::new();
+        for _ in 0..size {
+            let values = (0..rng.range(1..=5)).map(|_| *rng.choose(&cards)).collect::<Vec<_>>();
+            let hand = (0..5).map(|_| *rng.choose(&values)).collect::<String>();
+            input.push_str(&format!("{} {}\n", hand, rng.range(1..=1000)));
+        }
+        Some(input)
+    }
 }
 
 #[derive(Debug, PartialEq)]
-struct Bid {
-    hand: Hand,
-    bid: usize,
+pub struct Bid {
+    pub hand: Hand,
+    pub bid: usize,
 }
 
-fn analyze(lines: Vec<&str>, part: i32) -> usize {
-    let use_joker = part == 2;
-
-    let mut iter = lines.iter();
-    let mut bids = Vec::<Bid>::new();
-
-    while let Some(line) = iter.next() {
+impl Bid {
+    pub fn parse(line: &str) -> Result<Bid, ParseError> {
         let mut elements = line.split_whitespace();
-        let hand = Hand::from(elements.next().unwrap());
-        let bid = elements.next().unwrap().parse::<usize>().unwrap();
-        bids.push(Bid { hand, bid });
+        let hand = elements.next().ok_or_else(|| ParseError::new(DAY, line, line, "missing hand"))?;
+        let hand = Hand::parse(hand).map_err(|error| error.within(line, hand))?;
+        let bid = elements.next().ok_or_else(|| ParseError::new(DAY, line, &line[line.len()..], "missing bid"))?;
+        let bid = bid.parse::<usize>().map_err(|_| ParseError::new(DAY, line, bid, "invalid bid"))?;
+        Ok(Bid { hand, bid })
     }
+}
 
+pub fn parse_bids(input: &str) -> Result<Vec<Bid>, ParseError> {
+    input.lines().enumerate()
+        .map(|(line_index, line)| Bid::parse(line).map_err(|error| error.at_line(line_index + 1)))
+        .collect()
+}
+
+pub fn total_winnings(bids: &[Bid], use_joker: bool) -> Result<usize, SolveError> {
+    ranked_bids(bids, use_joker).iter().try_fold(0usize, |sum, (rank, bid)| {
+        let winnings = rank.checked_mul(bid.bid)
+            .ok_or_else(|| SolveError::overflow(DAY, &format!("the winnings of hand {} overflow", bid.hand.hand)))?;
+        sum.checked_add(winnings).ok_or_else(|| SolveError::overflow(DAY, "the total winnings overflow"))
+    })
+}
+
+// the bids from the strongest hand down with their rank, the weakest hand has rank 1
+pub fn ranked_bids(bids: &[Bid], use_joker: bool) -> Vec<(usize, &Bid)> {
+    let mut bids = bids.iter().collect::<Vec<_>>();
     bids.sort_by(|a, b| {
         b.hand.cmp(&a.hand, use_joker)
     });
 
     let mut bid_rank = bids.len();
-    let mut sum = 0usize;
+    let mut ranked = Vec::new();
     for bid in bids {
-        //println!("{:?} {} -> {} {}", bid.hand.hand, bid.bid, bid.hand.rank(), bid_rank);
-        sum += bid_rank * bid.bid;
+        event!(Level::Debug, DAY, "hand", hand = bid.hand.hand, bid = bid.bid, strength = bid.hand.rank(use_joker), rank = bid_rank);
+        ranked.push((bid_rank, bid));
         bid_rank -= 1;
     }
 
-    sum
+    ranked
 }
 
 #[derive(Debug, PartialEq)]
-struct Hand {
+pub struct Hand {
     hand: String,
     cards: Vec<Card>,
 }
@@ -55,6 +135,16 @@ impl Hand {
         Hand { hand, cards }
     }
 
+    pub fn parse(hand: &str) -> Result<Hand, ParseError> {
+        if let Some((index, card)) = hand.char_indices().find(|(_, card)| !CARDS.contains(*card)) {
+            return Err(ParseError::new(DAY, hand, &hand[index..index + card.len_utf8()], "invalid card"));
+        }
+        if hand.len() != 5 {
+            return Err(ParseError::new(DAY, hand, hand, "expected five cards"));
+        }
+        Ok(Hand::from(hand))
+    }
+
     fn from(hand: &str) -> Hand {
         let mut cards = hand.chars().fold(HashMap::<char, usize>::new(), |mut map, c| {
             *map.entry(c).or_insert(0) += 1;
@@ -64,7 +154,7 @@ impl Hand {
         cards.sort_by(|a, b| {
             let ordering = b.count.cmp(&a.count);
             if ordering == Ordering::Equal {
-                compare_cards(&b.value, &a.value)
+                compare_cards(&b.value, &a.value, CARDS)
             } else {
                 ordering
             }
@@ -73,11 +163,13 @@ impl Hand {
         Hand::new(String::from(hand), cards)
     }
 
-    fn cmp(&self, other: &Hand, use_joker: bool) -> Ordering {
+    // hands of the same type are ordered by their first differing card, jokers are the weakest card
+    pub fn cmp(&self, other: &Hand, use_joker: bool) -> Ordering {
+        let order = if use_joker { JOKER_CARDS } else { CARDS };
         let mut ordering = self.rank(use_joker).cmp(&other.rank(use_joker));
         if ordering == Ordering::Equal {
-            for i in 0..self.hand.len() {
-                ordering = compare_cards(&self.hand.chars().nth(i).unwrap(), &other.hand.chars().nth(i).unwrap());
+            for (a, b) in self.hand.chars().zip(other.hand.chars()) {
+                ordering = compare_cards(&a, &b, order);
                 if ordering != Ordering::Equal {
                     break;
                 }
@@ -86,7 +178,8 @@ impl Hand {
         ordering
     }
 
-    fn rank(&self, use_joker: bool) -> usize {
+    // the strength of the hand type, from 1 for a high card to 7 for five of a kind
+    pub fn rank(&self, use_joker: bool) -> usize {
         let mut cards = self.cards.clone();
 
         let joker_count = if use_joker {
@@ -102,7 +195,7 @@ impl Hand {
 
         let first_count = if joker_count == 5 { 5 } else { cards[0].count + joker_count };
 
-        return match first_count {
+        match first_count {
             5 => 7usize,
             4 => 6usize,
             3 => {
@@ -121,37 +214,36 @@ impl Hand {
                     2usize
                 }
             }
-            1 => if self.cards.len() == 5 {
-                1usize
-            } else {
-                0usize
-            },
+            1 if self.cards.len() == 5 => 1usize,
             _ => 0usize
-        };
+        }
     }
 }
 
-
 #[derive(Debug, PartialEq, Clone)]
-struct Card {
-    value: char,
-    count: usize,
+pub struct Card {
+    pub value: char,
+    pub count: usize,
 }
 
 impl Card {
-    fn new(value: char, count: usize) -> Card {
+    pub fn new(value: char, count: usize) -> Card {
         Card { value, count }
     }
 }
 
+const CARDS: &str = "AKQJT98765432";
+const JOKER_CARDS: &str = "AKQT98765432J";
 
-fn compare_cards(a: &char, b: &char) -> Ordering {
-    let order = "AKQJT98765432";
+// the names of the hand types indexed by their rank
+const HAND_TYPES: [&str; 8] = ["no hand", "high card", "one pair", "two pair", "three of a kind", "full house", "four of a kind", "five of a kind"];
 
+// compares two cards by their position in `order`, which lists the cards from the strongest down
+pub fn compare_cards(a: &char, b: &char, order: &str) -> Ordering {
     let a_index = order.find(*a).unwrap();
     let b_index = order.find(*b).unwrap();
 
-    return b_index.cmp(&a_index);
+    b_index.cmp(&a_index)
 }
 
 #[cfg(test)]
@@ -179,19 +271,41 @@ mod tests {
 
     #[test]
     fn test_complete() {
-        let input = r#"32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483"#;
-        let lines = input.lines().collect::<Vec<_>>();
+        let bids = parse_bids(EXAMPLES[0].input).unwrap();
 
-        assert_eq!(analyze(lines.clone(), 1), 6440);
-        assert_eq!(analyze(lines, 2), 5905);
+        assert_eq!(total_winnings(&bids, false), Ok(6440));
+        assert_eq!(total_winnings(&bids, true), Ok(5905));
     }
-}
 
-pub(crate) fn run() {
-    puzzle(1);
-    puzzle(2);
+    #[test]
+    fn test_joker_is_weakest_card() {
+        let bids = parse_bids("JKKK2 1\nTTTT2 2").unwrap();
+
+        assert_eq!(total_winnings(&bids, true), Ok(5));
+        assert_eq!(Hand::from("JKKK2").cmp(&Hand::from("2KKKK"), true), Ordering::Less);
+        assert_eq!(Hand::from("JKKK2").cmp(&Hand::from("TKKK3"), false), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_winnings_overflow() {
+        let bids = parse_bids("32T3K 1\nKK677 9223372036854775808").unwrap();
+        assert_eq!(total_winnings(&bids, false), Err(SolveError::overflow(DAY, "the winnings of hand KK677 overflow")));
+
+        let bids = parse_bids("32T3K 18446744073709551615\nKK677 1").unwrap();
+        assert_eq!(total_winnings(&bids, false), Err(SolveError::overflow(DAY, "the total winnings overflow")));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_bids("32T3K 765\nT55X5 684").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 4, "X"));
+
+        let error = Bid::parse(" KK67 28").unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (2, "expected five cards"));
+
+        let error = Bid::parse("KK677").unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (6, "missing bid"));
+
+        assert_eq!(Bid::parse("KK677 2B").unwrap_err().text, "2B");
+    }
 }
@@ -0,0 +1,39 @@
+use crate::solution::Puzzle;
+use crate::{puzzle01, puzzle02, puzzle03, puzzle04, puzzle05, puzzle06, puzzle07};
+
+/// All implemented days, ordered by day.
+pub static PUZZLES: &[&dyn Puzzle] = &[
+    &puzzle01::Day01,
+    &puzzle02::Day02,
+    &puzzle03::Day03,
+    &puzzle04::Day04,
+    &puzzle05::Day05,
+    &puzzle06::Day06,
+    &puzzle07::Day07,
+];
+
+pub fn days() -> Vec<u8> {
+    PUZZLES.iter().map(|puzzle| puzzle.day()).collect()
+}
+
+pub fn find(day: u8) -> Option<&'static dyn Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.day() == day).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        let days = days();
+
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(6).map(|puzzle| puzzle.title()), Some("Wait For It"));
+        assert!(find(25).is_none());
+    }
+}
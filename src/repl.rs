@@ -0,0 +1,62 @@
+use std::io;
+use std::io::{BufRead, Write};
+
+use crate::solution::{Part, Puzzle};
+
+/// Evaluates every line of `input` on its own and writes what each of `parts` makes of it.
+///
+/// `prompt` is written before every line, it is empty when the input is not typed in a terminal.
+pub fn repl<R: BufRead, W: Write>(puzzle: &dyn Puzzle, parts: &[Part], input: R, out: &mut W, prompt: &str) -> io::Result<()> {
+    write!(out, "{}", prompt)?;
+    out.flush()?;
+    for (line_index, line) in input.lines().enumerate() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            for part in parts {
+                // errors point at the line of the session
+                match puzzle.evaluate_line(&line, *part).map_err(|error| error.at_line(line_index + 1)) {
+                    Ok(value) => writeln!(out, "part {}: {}", part, value)?,
+                    Err(error) => writeln!(out, "part {}: error: {}", part, error)?,
+                }
+            }
+        }
+        write!(out, "{}", prompt)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle01::Day01;
+    use crate::puzzle03::Day03;
+    use crate::puzzle07::Day07;
+
+    fn evaluate(puzzle: &dyn Puzzle, input: &str) -> String {
+        let mut out = Vec::new();
+        repl(puzzle, &Part::ALL, input.as_bytes(), &mut out, "").unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_repl() {
+        assert_eq!(evaluate(&Day01, "xtwone3four\n\ntreb7uchet\n"), "part 1: 33\npart 2: 24\npart 1: 77\npart 2: 77\n");
+        assert_eq!(evaluate(&Day07, "KTJJT 220\nKTJJX"), "part 1: two pair (strength 3)\npart 2: four of a kind (strength 6)\n\
+                                                          part 1: error: day 7, line 2, column 5: invalid card 'X'\n\
+                                                          part 2: error: day 7, line 2, column 5: invalid card 'X'\n");
+    }
+
+    #[test]
+    fn test_default_evaluates_the_line_as_input() {
+        assert_eq!(evaluate(&Day03, "617*......"), "part 1: 617\npart 2: 0\n");
+    }
+
+    #[test]
+    fn test_prompt() {
+        let mut out = Vec::new();
+        repl(&Day01, &[Part::One], "12".as_bytes(), &mut out, "> ").unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "> part 1: 12\n> ");
+    }
+}
@@ -0,0 +1,179 @@
+use std::io;
+use std::io::Write;
+use std::time::Duration;
+
+use crate::runner::Record;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// Writes records as they are produced; json and csv output include failed parts with status `error`.
+pub struct Reporter<W: Write> {
+    format: Format,
+    timings: bool,
+    out: W,
+    count: usize,
+    last_error: Option<String>,
+}
+
+impl<W: Write> Reporter<W> {
+    // `timings` adds parse and solve times to the text output, json and csv always contain them
+    pub fn new(format: Format, timings: bool, mut out: W) -> io::Result<Reporter<W>> {
+        match format {
+            Format::Text => {}
+            Format::Json => write!(out, "[")?,
+            Format::Csv => writeln!(out, "day,part,status,answer,input,parse_ms,solve_ms,elapsed_ms,error")?,
+        }
+        Ok(Reporter { format, timings, out, count: 0, last_error: None })
+    }
+
+    pub fn record(&mut self, record: &Record) -> io::Result<()> {
+        match self.format {
+            Format::Text => match &record.result {
+                Ok(answer) if self.timings => writeln!(self.out, "day {} part {}: {} (parse {} ms, solve {} ms)",
+                                                       record.day, record.part, answer, milliseconds(record.parse_time), milliseconds(record.solve_time))?,
+                Ok(answer) => writeln!(self.out, "day {} part {}: {}", record.day, record.part, answer)?,
+                // a broken input fails every part with the same error, report it once
+                Err(error) if self.last_error.as_ref() != Some(error) => {
+                    eprintln!("error: {}", error);
+                    self.last_error = Some(error.clone());
+                }
+                Err(_) => {}
+            },
+            Format::Json => {
+                let separator = if self.count == 0 { "" } else { "," };
+                let (answer, error) = match &record.result {
+                    Ok(answer) => (answer.to_string(), "null".to_string()),
+                    Err(error) => ("null".to_string(), json_string(error)),
+                };
+                write!(self.out, "{}\n  {{\"day\": {}, \"part\": {}, \"status\": \"{}\", \"answer\": {}, \"input\": {}, \"parse_ms\": {}, \"solve_ms\": {}, \"elapsed_ms\": {}, \"error\": {}}}",
+                       separator, record.day, record.part, status(record), answer, json_string(&record.input),
+                       milliseconds(record.parse_time), milliseconds(record.solve_time), milliseconds(record.elapsed()), error)?;
+            }
+            Format::Csv => {
+                let (answer, error) = match &record.result {
+                    Ok(answer) => (answer.to_string(), String::new()),
+                    Err(error) => (String::new(), csv_field(error)),
+                };
+                writeln!(self.out, "{},{},{},{},{},{},{},{},{}",
+                         record.day, record.part, status(record), answer, csv_field(&record.input),
+                         milliseconds(record.parse_time), milliseconds(record.solve_time), milliseconds(record.elapsed()), error)?;
+            }
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        if self.format == Format::Json {
+            writeln!(self.out, "{}]", if self.count == 0 { "" } else { "\n" })?;
+        }
+        self.out.flush()
+    }
+}
+
+// reports all records and finishes, returns whether all parts were solved
+pub fn write_all<W: Write>(mut reporter: Reporter<W>, records: impl IntoIterator<Item = Record>) -> io::Result<bool> {
+    let mut success = true;
+    for record in records {
+        success &= record.result.is_ok();
+        reporter.record(&record)?;
+    }
+    reporter.finish()?;
+    Ok(success)
+}
+
+fn status(record: &Record) -> &'static str {
+    if record.result.is_ok() { "ok" } else { "error" }
+}
+
+pub fn milliseconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if (character as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", character as u32)),
+            character => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Part;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record { day: 6, part: Part::One, input: String::from("./06_data.txt"), parse_time: Duration::from_micros(500), solve_time: Duration::from_micros(1000), result: Ok(288) },
+            Record { day: 7, part: Part::Two, input: String::from("./07_data.txt"), parse_time: Duration::ZERO, solve_time: Duration::ZERO, result: Err(String::from("day 7, line 2, column 1: invalid card 'Y'")) },
+        ]
+    }
+
+    fn write(format: Format, records: &[Record]) -> String {
+        write_with_timings(format, false, records)
+    }
+
+    fn write_with_timings(format: Format, timings: bool, records: &[Record]) -> String {
+        let mut out = Vec::new();
+        let mut reporter = Reporter::new(format, timings, &mut out).unwrap();
+        for record in records {
+            reporter.record(record).unwrap();
+        }
+        reporter.finish().unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(write(Format::Json, &records()), r#"[
+  {"day": 6, "part": 1, "status": "ok", "answer": 288, "input": "./06_data.txt", "parse_ms": 0.500, "solve_ms": 1.000, "elapsed_ms": 1.500, "error": null},
+  {"day": 7, "part": 2, "status": "error", "answer": null, "input": "./07_data.txt", "parse_ms": 0.000, "solve_ms": 0.000, "elapsed_ms": 0.000, "error": "day 7, line 2, column 1: invalid card 'Y'"}
+]
+"#);
+        assert_eq!(write(Format::Json, &[]), "[]\n");
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(write(Format::Csv, &records()), r#"day,part,status,answer,input,parse_ms,solve_ms,elapsed_ms,error
+6,1,ok,288,./06_data.txt,0.500,1.000,1.500,
+7,2,error,,./07_data.txt,0.000,0.000,0.000,"day 7, line 2, column 1: invalid card 'Y'"
+"#);
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(write(Format::Text, &records()), "day 6 part 1: 288\n");
+        assert_eq!(write_with_timings(Format::Text, true, &records()), "day 6 part 1: 288 (parse 0.500 ms, solve 1.000 ms)\n");
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(json_string("a \"b\"\\\n\u{1}"), r#""a \"b\"\\\n\u0001""#);
+        assert_eq!(csv_field("say \"hi\""), r#""say ""hi""""#);
+        assert_eq!(csv_field("plain"), "plain");
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::input::InputSource;
+use crate::solution::{Answer, Part, Prepared, Puzzle};
+
+/// The outcome of solving one part of one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    // the day's input is parsed once, every part reports the time of that shared parse
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    pub result: Result<Answer, String>,
+}
+
+impl Record {
+    pub fn elapsed(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+}
+
+pub fn run(puzzle: &dyn Puzzle, parts: &[Part], source: &InputSource) -> Vec<Record> {
+    let prepared = prepare(puzzle, source);
+    parts.iter().map(|part| solve(puzzle.day(), &prepared, *part, &source.name(puzzle.day()))).collect()
+}
+
+// solves the parts of `parts` the example has an expected answer for, the input is named `example N`
+pub fn run_example(puzzle: &dyn Puzzle, parts: &[Part], index: usize) -> Vec<Record> {
+    let example = &puzzle.examples()[index];
+    let prepared = parse(puzzle, example.input);
+    parts.iter().filter(|part| example.expected(**part).is_some())
+        .map(|part| solve(puzzle.day(), &prepared, *part, &format!("example {}", index + 1))).collect()
+}
+
+// the records of every example of `puzzles`, see `run_example`
+pub fn run_examples(puzzles: &[&dyn Puzzle], parts: &[Part]) -> Vec<Record> {
+    puzzles.iter().flat_map(|puzzle| (0..puzzle.examples().len()).flat_map(move |index| run_example(*puzzle, parts, index))).collect()
+}
+
+/// Runs `puzzles` on up to `jobs` threads: every input is parsed once, then all day/part pairs are solved.
+/// The records are ordered like the sequential [`run`] of each puzzle, whatever order the jobs finish in.
+pub fn run_all(puzzles: &[&dyn Puzzle], parts: &[Part], source: &InputSource, jobs: usize) -> Vec<Record> {
+    let inputs = puzzles.iter().map(|puzzle| (*puzzle, source.read(puzzle.day()).map_err(|error| error.to_string()))).collect::<Vec<_>>();
+    run_inputs(&inputs, parts, source, jobs)
+}
+
+/// Like [`run_all`], but on inputs that were already read from `source`, an `Err` is the read error of the day.
+pub fn run_inputs(inputs: &[(&dyn Puzzle, Result<String, String>)], parts: &[Part], source: &InputSource, jobs: usize) -> Vec<Record> {
+    let prepared = parallel_map(inputs, jobs, |(puzzle, input)| input.clone().and_then(|input| parse(*puzzle, &input)));
+
+    let tasks = inputs.iter().zip(&prepared)
+        .flat_map(|((puzzle, _), prepared)| parts.iter().map(move |part| (puzzle.day(), prepared, *part)))
+        .collect::<Vec<_>>();
+    parallel_map(&tasks, jobs, |(day, prepared, part)| solve(*day, prepared, *part, &source.name(*day)))
+}
+
+type Preparation<'a> = Result<(Box<dyn Prepared + 'a>, Duration), String>;
+
+fn prepare<'a>(puzzle: &'a dyn Puzzle, source: &InputSource) -> Preparation<'a> {
+    let input = source.read(puzzle.day()).map_err(|error| error.to_string())?;
+    parse(puzzle, &input)
+}
+
+fn parse<'a>(puzzle: &'a dyn Puzzle, input: &str) -> Preparation<'a> {
+    let start = Instant::now();
+    let prepared = puzzle.prepare(input).map_err(|error| error.to_string())?;
+    Ok((prepared, start.elapsed()))
+}
+
+fn solve(day: u8, prepared: &Preparation, part: Part, input: &str) -> Record {
+    let record = |parse_time, solve_time, result| Record { day, part, input: input.to_string(), parse_time, solve_time, result };
+    match prepared {
+        Ok((prepared, parse_time)) => {
+            let start = Instant::now();
+            let result = prepared.solve(part).map_err(|error| error.to_string());
+            record(*parse_time, start.elapsed(), result)
+        }
+        Err(error) => record(Duration::ZERO, Duration::ZERO, Err(error.clone())),
+    }
+}
+
+// applies `f` to all items on up to `jobs` scoped threads, the results keep the order of the items
+fn parallel_map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<Option<R>>>());
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else { break };
+                let result = f(item);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+    results.into_inner().unwrap().into_iter().map(|result| result.expect("every item is processed")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{find, PUZZLES};
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let items = (0..100).collect::<Vec<u64>>();
+
+        assert_eq!(parallel_map(&items, 8, |item| item * 2), items.iter().map(|item| item * 2).collect::<Vec<_>>());
+        assert_eq!(parallel_map(&[] as &[u64], 4, |item| *item), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_run_all_matches_sequential_run() {
+        let source = InputSource::Directory(std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+        let sequential = PUZZLES.iter().flat_map(|puzzle| run(*puzzle, &Part::ALL, &source)).collect::<Vec<_>>();
+        let parallel = run_all(PUZZLES, &Part::ALL, &source, 4);
+
+        let key = |record: &Record| (record.day, record.part, record.result.clone());
+        assert_eq!(parallel.iter().map(key).collect::<Vec<_>>(), sequential.iter().map(key).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_run_inputs_solves_the_given_inputs() {
+        let source = InputSource::Directory(std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+        let inputs = [(find(6).unwrap(), Ok(String::from("Time: 7\nDistance: 9"))), (find(7).unwrap(), Err(String::from("unreadable")))];
+        let records = run_inputs(&inputs, &[Part::One], &source, 2);
+
+        assert_eq!(records.iter().map(|record| (record.day, record.result.clone())).collect::<Vec<_>>(),
+                   vec![(6, Ok(4)), (7, Err(String::from("unreadable")))]);
+        assert_eq!(records[0].input, source.name(6));
+    }
+
+    #[test]
+    fn test_examples_give_expected_answers() {
+        for puzzle in PUZZLES {
+            for (index, example) in puzzle.examples().iter().enumerate() {
+                for record in run_example(*puzzle, &Part::ALL, index) {
+                    assert_eq!(record.result.ok(), example.expected(record.part), "day {} example {} part {}", record.day, index + 1, record.part);
+                }
+            }
+        }
+    }
+}
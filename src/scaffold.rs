@@ -0,0 +1,216 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    Io { path: PathBuf, error: io::Error },
+    // the file does not contain the lines the registration is inserted next to
+    Unrecognized(PathBuf),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "'{}' already exists", path.display()),
+            ScaffoldError::Io { path, error } => write!(f, "cannot access '{}': {}", path.display(), error),
+            ScaffoldError::Unrecognized(path) => write!(f, "cannot find where to register the new day in '{}'", path.display()),
+        }
+    }
+}
+
+// writes `src/puzzleNN.rs` below `root` and registers it in `src/lib.rs` and `src/registry.rs`, returns the touched files
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let module = root.join("src").join(format!("puzzle{:02}.rs", day));
+    if module.exists() {
+        return Err(ScaffoldError::Exists(module));
+    }
+    let lib = root.join("src").join("lib.rs");
+    let registry = root.join("src").join("registry.rs");
+
+    let read = |path: &PathBuf| fs::read_to_string(path).map_err(|error| ScaffoldError::Io { path: path.clone(), error });
+    let lib_source = register_module(&read(&lib)?, day).ok_or_else(|| ScaffoldError::Unrecognized(lib.clone()))?;
+    let registry_source = register_puzzle(&read(&registry)?, day).ok_or_else(|| ScaffoldError::Unrecognized(registry.clone()))?;
+
+    let write = |path: &PathBuf, content: &str| fs::write(path, content).map_err(|error| ScaffoldError::Io { path: path.clone(), error });
+    write(&module, &module_source(day))?;
+    write(&lib, &lib_source)?;
+    write(&registry, &registry_source)?;
+    Ok(vec![module, lib, registry])
+}
+
+pub fn module_source(day: u8) -> String {
+    MODULE_TEMPLATE.replace("{NN}", &format!("{:02}", day)).replace("{N}", &day.to_string())
+}
+
+const MODULE_TEMPLATE: &str = r##"use crate::error::{ParseError, SolveError};
+use crate::generate::Rng;
+use crate::solution::{Answer, Example, Solution};
+
+const DAY: u8 = {N};
+
+pub struct Day{NN};
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        input: "1\n2\n3",
+        part1: Some(6),
+        part2: Some(6),
+    },
+];
+
+impl Solution for Day{NN} {
+    type Model = Vec<usize>;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Day {N}"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, ParseError> {
+        parse_values(input)
+    }
+
+    fn part1(&self, values: &Vec<usize>) -> Result<Answer, SolveError> {
+        Ok(solve_part1(values) as Answer)
+    }
+
+    fn part2(&self, values: &Vec<usize>) -> Result<Answer, SolveError> {
+        Ok(solve_part2(values) as Answer)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some((0..size).map(|_| format!("{}\n", rng.below(1000))).collect())
+    }
+}
+
+pub fn parse_values(input: &str) -> Result<Vec<usize>, ParseError> {
+    input.lines().enumerate()
+        .map(|(line_index, line)| parse_line(line).map_err(|error| error.at_line(line_index + 1)))
+        .collect()
+}
+
+fn parse_line(line: &str) -> Result<usize, ParseError> {
+    line.trim().parse::<usize>().map_err(|_| ParseError::new(DAY, line, line.trim(), "invalid number"))
+}
+
+pub fn solve_part1(values: &[usize]) -> usize {
+    values.iter().sum()
+}
+
+pub fn solve_part2(values: &[usize]) -> usize {
+    values.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete() {
+        let values = parse_values(EXAMPLES[0].input).unwrap();
+
+        assert_eq!(solve_part1(&values), 6);
+        assert_eq!(solve_part2(&values), 6);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_values("1\nx").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 1, "x"));
+    }
+}
+"##;
+
+fn register_module(lib: &str, day: u8) -> Option<String> {
+    insert_sorted(lib, &format!("pub mod puzzle{:02};", day), |line| line.starts_with("pub mod puzzle"))
+}
+
+fn register_puzzle(registry: &str, day: u8) -> Option<String> {
+    let module = format!("puzzle{:02}", day);
+    let registry = insert_sorted(registry, &format!("    &{}::Day{:02},", module, day), |line| line.starts_with("    &puzzle"))?;
+
+    let mut found = false;
+    let lines = registry.lines().map(|line| {
+        match line.strip_prefix("use crate::{").and_then(|line| line.strip_suffix("};")) {
+            Some(modules) if modules.starts_with("puzzle") => {
+                found = true;
+                let mut modules = modules.split(", ").chain([module.as_str()]).collect::<Vec<_>>();
+                modules.sort();
+                format!("use crate::{{{}}};", modules.join(", "))
+            }
+            _ => line.to_string(),
+        }
+    }).collect::<Vec<_>>();
+    found.then(|| lines.join("\n") + "\n")
+}
+
+// inserts `line` into the sorted block of lines matching `block`, `None` if there is no such block
+fn insert_sorted(source: &str, line: &str, block: impl Fn(&str) -> bool) -> Option<String> {
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let last = lines.iter().rposition(|existing| block(existing))?;
+    let position = lines.iter().position(|existing| block(existing) && *existing > line).unwrap_or(last + 1);
+    lines.insert(position, line);
+    Some(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = r#"use crate::solution::Puzzle;
+use crate::{puzzle01, puzzle03};
+
+pub static PUZZLES: &[&dyn Puzzle] = &[
+    &puzzle01::Day01,
+    &puzzle03::Day03,
+];
+"#;
+
+    #[test]
+    fn test_register_module() {
+        let lib = "pub mod error;\npub mod puzzle01;\npub mod puzzle03;\npub mod registry;\n";
+
+        assert_eq!(register_module(lib, 2).unwrap(), "pub mod error;\npub mod puzzle01;\npub mod puzzle02;\npub mod puzzle03;\npub mod registry;\n");
+        assert_eq!(register_module(lib, 12).unwrap(), "pub mod error;\npub mod puzzle01;\npub mod puzzle03;\npub mod puzzle12;\npub mod registry;\n");
+        assert_eq!(register_module("pub mod error;\n", 2), None);
+    }
+
+    #[test]
+    fn test_register_puzzle() {
+        assert_eq!(register_puzzle(REGISTRY, 2).unwrap(), r#"use crate::solution::Puzzle;
+use crate::{puzzle01, puzzle02, puzzle03};
+
+pub static PUZZLES: &[&dyn Puzzle] = &[
+    &puzzle01::Day01,
+    &puzzle02::Day02,
+    &puzzle03::Day03,
+];
+"#);
+    }
+
+    #[test]
+    fn test_module_source() {
+        let source = module_source(8);
+
+        assert!(source.contains("const DAY: u8 = 8;\n"));
+        assert!(source.contains("impl Solution for Day08 {"));
+        assert!(!source.contains("{N"));
+    }
+
+    #[test]
+    fn test_existing_module_is_kept() {
+        let error = scaffold(Path::new(env!("CARGO_MANIFEST_DIR")), 1).unwrap_err();
+
+        assert!(matches!(error, ScaffoldError::Exists(_)));
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::error::{ParseError, SolveError};
+use crate::generate::Rng;
+
+pub type Answer = u64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn parse(value: &str) -> Option<Part> {
+        match value {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Part::One => "1",
+            Part::Two => "2",
+        })
+    }
+}
+
+/// An example from the puzzle description, with the expected answers of the parts it is given for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<Answer> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+/// One item's share of an answer, e.g. the calibration value of a line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contribution {
+    pub item: String,
+    pub value: Answer,
+}
+
+impl Contribution {
+    pub fn new(item: impl Into<String>, value: usize) -> Contribution {
+        Contribution { item: item.into(), value: value as Answer }
+    }
+}
+
+/// A single day of the calendar: parses the puzzle input once into a model and solves both parts on it.
+pub trait Solution: Sync {
+    type Model: Send + Sync;
+
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn examples(&self) -> &'static [Example];
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError>;
+
+    fn part1(&self, model: &Self::Model) -> Result<Answer, SolveError>;
+
+    fn part2(&self, model: &Self::Model) -> Result<Answer, SolveError>;
+
+    // the items whose values add up to the answer of `part`, empty for days whose answer is no such sum
+    fn explain(&self, _model: &Self::Model, _part: Part) -> Vec<Contribution> {
+        Vec::new()
+    }
+
+    // what `part` makes of a single line, by default the answer for an input of just that line
+    fn evaluate_line(&self, line: &str, part: Part) -> Result<String, SolveError> {
+        let model = self.parse(line)?;
+        let answer = match part {
+            Part::One => self.part1(&model)?,
+            Part::Two => self.part2(&model)?,
+        };
+        Ok(answer.to_string())
+    }
+
+    // a random but valid input with `size` items for stress tests, `None` if the day has no generator
+    fn generate(&self, _rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+/// Object safe view of a [`Solution`], used by the registry to hold days with different models.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn examples(&self) -> &'static [Example];
+
+    fn prepare<'a>(&'a self, input: &str) -> Result<Box<dyn Prepared + 'a>, ParseError>;
+
+    fn evaluate_line(&self, line: &str, part: Part) -> Result<String, SolveError>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String>;
+}
+
+/// The parsed input of a [`Puzzle`], ready to be solved.
+pub trait Prepared: Send + Sync {
+    fn solve(&self, part: Part) -> Result<Answer, SolveError>;
+
+    fn explain(&self, part: Part) -> Vec<Contribution>;
+}
+
+struct Parsed<'a, S: Solution> {
+    solution: &'a S,
+    model: S::Model,
+}
+
+impl<S: Solution> Prepared for Parsed<'_, S> {
+    fn solve(&self, part: Part) -> Result<Answer, SolveError> {
+        match part {
+            Part::One => self.solution.part1(&self.model),
+            Part::Two => self.solution.part2(&self.model),
+        }
+    }
+
+    fn explain(&self, part: Part) -> Vec<Contribution> {
+        self.solution.explain(&self.model, part)
+    }
+}
+
+impl<S: Solution> Puzzle for S {
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solution::title(self)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        Solution::examples(self)
+    }
+
+    fn prepare<'a>(&'a self, input: &str) -> Result<Box<dyn Prepared + 'a>, ParseError> {
+        Ok(Box::new(Parsed { solution: self, model: self.parse(input)? }))
+    }
+
+    fn evaluate_line(&self, line: &str, part: Part) -> Result<String, SolveError> {
+        Solution::evaluate_line(self, line, part)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Solution::generate(self, rng, size)
+    }
+}
+
+pub fn lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part() {
+        assert_eq!(Part::parse("1"), Some(Part::One));
+        assert_eq!(Part::parse("2"), Some(Part::Two));
+        assert_eq!(Part::parse("3"), None);
+        assert_eq!(format!("{:>3}", Part::Two), "  2");
+    }
+}
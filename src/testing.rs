@@ -0,0 +1,11 @@
+//! Helpers shared by the unit tests.
+
+use std::fs;
+use std::path::PathBuf;
+
+// a directory below the system temp directory named after the test and the process, so parallel runs do not collide
+pub fn temp_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("aoc-2023-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
@@ -0,0 +1,64 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How detailed a solver event is, `-v` enables debug events and `-vv` adds trace events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug = 1,
+    Trace = 2,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    VERBOSITY.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Reports a solver event on stderr when its level is enabled, the fields are formatted with `Debug`:
+///
+/// ```
+/// aoc_2023::event!(aoc_2023::trace::Level::Debug, 7, "hand", hand = "KTJJT", rank = 3);
+/// ```
+///
+/// writes `debug day=7 event=hand hand="KTJJT" rank=3`. The fields are only formatted when the level is enabled.
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $day:expr, $name:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled($level) {
+            $crate::trace::emit($level, $day, $name, &[$((stringify!($key), format!("{:?}", $value))),*]);
+        }
+    };
+}
+
+pub fn emit(level: Level, day: u8, name: &str, fields: &[(&str, String)]) {
+    eprintln!("{}", format_event(level, day, name, fields));
+}
+
+pub fn format_event(level: Level, day: u8, name: &str, fields: &[(&str, String)]) -> String {
+    let level = match level {
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+    };
+    let mut line = format!("{} day={} event={}", level, day, name);
+    for (key, value) in fields {
+        _ = write!(line, " {}={}", key, value);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_event() {
+        let fields = [("hand", format!("{:?}", "KTJJT")), ("rank", 3.to_string())];
+
+        assert_eq!(format_event(Level::Debug, 7, "hand", &fields), r#"debug day=7 event=hand hand="KTJJT" rank=3"#);
+        assert_eq!(format_event(Level::Trace, 5, "ranges", &[]), "trace day=5 event=ranges");
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+use crate::runner::Record;
+use crate::solution::{Answer, Example, Part};
+
+/// Expected answers keyed by day and part, read from a toml file such as
+///
+/// ```toml
+/// [day05]
+/// part1 = 35
+/// part2 = 46
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), Answer>,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: String, error: io::Error },
+    Syntax { line: usize, text: String, message: &'static str },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io { path, error } => write!(f, "cannot read answers '{}': {}", path, error),
+            AnswersError::Syntax { line, text, message } => write!(f, "answers line {}: {} '{}'", line, message, text),
+        }
+    }
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        let content = read_to_string(path).map_err(|error| AnswersError::Io { path: path.display().to_string(), error })?;
+        Answers::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Answers, AnswersError> {
+        let mut answers = BTreeMap::new();
+        let mut day: Option<u8> = None;
+
+        for (line_index, line) in content.lines().enumerate() {
+            let syntax = |message| AnswersError::Syntax { line: line_index + 1, text: line.to_string(), message };
+            let line = line.split_once('#').map_or(line, |(content, _)| content).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                day = Some(section.trim().strip_prefix("day").and_then(|day| day.parse().ok())
+                    .ok_or_else(|| syntax("expected a section like [day05]"))?);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| syntax("expected 'part1 = <answer>'"))?;
+            let part = match key.trim() {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(syntax("expected part1 or part2")),
+            };
+            let value = value.trim();
+            let value = value.strip_prefix('"').and_then(|value| value.strip_suffix('"')).unwrap_or(value);
+            let answer = value.parse::<Answer>().map_err(|_| syntax("invalid answer"))?;
+            let day = day.ok_or_else(|| syntax("answer outside of a [dayNN] section"))?;
+            answers.insert((day, part), answer);
+        }
+
+        Ok(Answers { answers })
+    }
+
+    // the answers an example of `day` is given with
+    pub fn of_example(day: u8, example: &Example) -> Answers {
+        Answers { answers: Part::ALL.iter().filter_map(|part| Some(((day, *part), example.expected(*part)?))).collect() }
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<Answer> {
+        self.answers.get(&(day, part)).copied()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Match,
+    Mismatch,
+    Unverified,
+    Failed(String),
+}
+
+impl Verdict {
+    pub fn of(record: &Record, answers: &Answers) -> Verdict {
+        match (&record.result, answers.get(record.day, record.part)) {
+            (Err(error), _) => Verdict::Failed(error.clone()),
+            (Ok(_), None) => Verdict::Unverified,
+            (Ok(answer), Some(expected)) if *answer == expected => Verdict::Match,
+            (Ok(_), Some(_)) => Verdict::Mismatch,
+        }
+    }
+
+    // answers without an expected value are reported, but do not fail the verification
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Mismatch | Verdict::Failed(_))
+    }
+}
+
+pub fn write_header<W: Write>(out: &mut W) -> io::Result<()> {
+    writeln!(out, "{:>3} {:>4} {:>20} {:>20}  status", "day", "part", "expected", "actual")
+}
+
+pub fn write_row<W: Write>(out: &mut W, record: &Record, answers: &Answers) -> io::Result<Verdict> {
+    let verdict = Verdict::of(record, answers);
+    let expected = answers.get(record.day, record.part).map_or("-".to_string(), |answer| answer.to_string());
+    let actual = record.result.as_ref().map_or("-".to_string(), |answer| answer.to_string());
+    let status = match &verdict {
+        Verdict::Match => "ok".to_string(),
+        Verdict::Mismatch => "MISMATCH".to_string(),
+        Verdict::Unverified => "unverified".to_string(),
+        Verdict::Failed(error) => format!("ERROR {}", error),
+    };
+    writeln!(out, "{:>3} {:>4} {:>20} {:>20}  {}", record.day, record.part, expected, actual, status)?;
+    Ok(verdict)
+}
+
+// writes the table and a summary, returns whether no answer differed from the expected one and no part failed
+pub fn write_verification<W: Write>(out: &mut W, records: &[Record], answers: &Answers) -> io::Result<bool> {
+    let (mut failed, mut unverified) = (0, 0);
+    write_header(out)?;
+    for record in records {
+        match write_row(out, record, answers)? {
+            Verdict::Unverified => unverified += 1,
+            verdict if verdict.is_failure() => failed += 1,
+            _ => {}
+        }
+    }
+    if failed == 0 {
+        writeln!(out, "{} answers verified, {} without an expected answer", records.len() - unverified, unverified)?;
+    } else {
+        writeln!(out, "{} of {} answers failed verification", failed, records.len())?;
+    }
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const ANSWERS: &str = r#"
+# expected answers
+[day05]
+part1 = 35
+part2 = "46"  # quoted values are accepted as well
+
+[day6]
+part1 = 288
+"#;
+
+    fn record(day: u8, part: Part, result: Result<Answer, String>) -> Record {
+        Record { day, part, input: String::from("-"), parse_time: Duration::ZERO, solve_time: Duration::ZERO, result }
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(answers.get(5, Part::One), Some(35));
+        assert_eq!(answers.get(5, Part::Two), Some(46));
+        assert_eq!(answers.get(6, Part::One), Some(288));
+        assert_eq!(answers.get(6, Part::Two), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Answers::parse("part1 = 1").unwrap_err().to_string(), "answers line 1: answer outside of a [dayNN] section 'part1 = 1'");
+        assert_eq!(Answers::parse("[day1]\npart3 = 1").unwrap_err().to_string(), "answers line 2: expected part1 or part2 'part3 = 1'");
+        assert!(Answers::parse("[day1]\npart1 = x").is_err());
+        assert!(Answers::parse("[days]").is_err());
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(Verdict::of(&record(5, Part::One, Ok(35)), &answers), Verdict::Match);
+        assert_eq!(Verdict::of(&record(5, Part::Two, Ok(47)), &answers), Verdict::Mismatch);
+        assert_eq!(Verdict::of(&record(6, Part::Two, Ok(71503)), &answers), Verdict::Unverified);
+        assert_eq!(Verdict::of(&record(6, Part::One, Err(String::from("broken"))), &answers), Verdict::Failed(String::from("broken")));
+        assert!(!Verdict::Unverified.is_failure());
+    }
+
+    #[test]
+    fn test_table() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        let mut out = Vec::new();
+        write_row(&mut out, &record(5, Part::Two, Ok(47)), &answers).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "  5    2                   46                   47  MISMATCH\n");
+    }
+
+    #[test]
+    fn test_summary() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        let mut out = Vec::new();
+        assert!(write_verification(&mut out, &[record(5, Part::One, Ok(35)), record(6, Part::Two, Ok(71503))], &answers).unwrap());
+        assert!(String::from_utf8(out).unwrap().ends_with("\n1 answers verified, 1 without an expected answer\n"));
+
+        let mut out = Vec::new();
+        assert!(!write_verification(&mut out, &[record(5, Part::One, Ok(35)), record(5, Part::Two, Err(String::from("broken")))], &answers).unwrap());
+        assert!(String::from_utf8(out).unwrap().ends_with("\n1 of 2 answers failed verification\n"));
+    }
+
+    #[test]
+    fn test_answers_of_example() {
+        let example = Example { input: "", part1: Some(288), part2: None };
+        let answers = Answers::of_example(6, &example);
+
+        assert_eq!((answers.get(6, Part::One), answers.get(6, Part::Two), answers.get(5, Part::One)), (Some(288), None, None));
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::input::InputSource;
+use crate::report::milliseconds;
+use crate::runner;
+use crate::runner::Record;
+use crate::solution::{Part, Puzzle};
+use crate::verify::{Answers, Verdict};
+
+/// Detects changed files by polling their modification time and size, so no file system notifications are needed.
+#[derive(Debug, Default)]
+pub struct Watcher {
+    stamps: HashMap<PathBuf, Option<(SystemTime, u64)>>,
+}
+
+impl Watcher {
+    // returns the paths that were created, modified or removed since the last poll, every path on the first poll
+    pub fn poll(&mut self, paths: &[PathBuf]) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        let mut stamps = HashMap::new();
+        for path in paths {
+            let stamp = fs::metadata(path).ok().and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+            if self.stamps.get(path) != Some(&stamp) {
+                changed.push(path.clone());
+            }
+            stamps.insert(path.clone(), stamp);
+        }
+        changed.extend(self.stamps.keys().filter(|path| !stamps.contains_key(*path)).cloned());
+        self.stamps = stamps;
+        changed
+    }
+}
+
+/// Runs `puzzle` whenever its input or its example files change, polling every `interval` until interrupted.
+///
+/// Running changed solver code needs a rebuild, an edit of the day's module in the crate sources is only reported.
+pub fn watch<W: Write>(out: &mut W, puzzle: &dyn Puzzle, parts: &[Part], source: &InputSource, answers: Option<&Answers>,
+                       interval: Duration) -> io::Result<()> {
+    let module = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join(format!("puzzle{:02}.rs", puzzle.day()));
+    let mut watcher = Watcher::default();
+    let mut first = true;
+    loop {
+        let examples = example_files(source, puzzle.day());
+        let inputs = source.path(puzzle.day()).into_iter().chain(examples.iter().cloned()).collect::<Vec<_>>();
+        let changed = watcher.poll(&inputs.iter().cloned().chain([module.clone()]).collect::<Vec<_>>());
+
+        if first {
+            let names = inputs.iter().map(|path| path.display().to_string()).collect::<Vec<_>>();
+            writeln!(out, "watching {} (ctrl-c to stop)", names.join(", "))?;
+        } else if changed.contains(&module) {
+            writeln!(out, "\n{} changed, rebuild and restart watch to run the new code", module.display())?;
+        }
+        if first || changed.iter().any(|path| *path != module) {
+            writeln!(out)?;
+            write_run(out, puzzle, parts, source, answers, &examples)?;
+            out.flush()?;
+        }
+        first = false;
+        thread::sleep(interval);
+    }
+}
+
+// runs the input, the example files and the examples of the puzzle description, the embedded examples are verified
+// against their expected answers
+pub fn write_run<W: Write>(out: &mut W, puzzle: &dyn Puzzle, parts: &[Part], source: &InputSource, answers: Option<&Answers>,
+                           examples: &[PathBuf]) -> io::Result<()> {
+    let day = puzzle.day();
+    for record in runner::run(puzzle, parts, source) {
+        write_record(out, &format!("day {}", day), &record, answers)?;
+    }
+    for example in examples {
+        let label = example.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        for record in runner::run(puzzle, parts, &InputSource::File(example.clone())) {
+            write_record(out, &label, &record, None)?;
+        }
+    }
+    for (index, example) in puzzle.examples().iter().enumerate() {
+        let expected = Answers::of_example(day, example);
+        for record in runner::run_example(puzzle, parts, index) {
+            write_record(out, &format!("example {}", index + 1), &record, Some(&expected))?;
+        }
+    }
+    Ok(())
+}
+
+// example inputs are stored next to the data file as `NN_example*.txt`
+pub fn example_files(source: &InputSource, day: u8) -> Vec<PathBuf> {
+    let Some(directory) = source.path(day).and_then(|path| path.parent().map(Path::to_path_buf)) else {
+        return Vec::new();
+    };
+    let prefix = format!("{:02}_example", day);
+    let mut examples = fs::read_dir(if directory.as_os_str().is_empty() { Path::new(".") } else { &directory })
+        .map(|entries| entries.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.file_name().and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt")))
+            .collect::<Vec<_>>())
+        .unwrap_or_default();
+    examples.sort();
+    examples
+}
+
+pub fn write_record<W: Write>(out: &mut W, label: &str, record: &Record, answers: Option<&Answers>) -> io::Result<()> {
+    match &record.result {
+        Ok(answer) => {
+            write!(out, "{} part {}: {} (parse {} ms, solve {} ms)",
+                   label, record.part, answer, milliseconds(record.parse_time), milliseconds(record.solve_time))?;
+            match answers.map(|answers| (Verdict::of(record, answers), answers.get(record.day, record.part))) {
+                Some((Verdict::Match, _)) => writeln!(out, " ok"),
+                Some((Verdict::Mismatch, Some(expected))) => writeln!(out, " MISMATCH, expected {}", expected),
+                _ => writeln!(out),
+            }
+        }
+        Err(error) => writeln!(out, "{} part {}: error: {}", label, record.part, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle06::{Day06, EXAMPLES};
+    use crate::testing::temp_directory;
+
+    #[test]
+    fn test_poll() {
+        let directory = temp_directory("watch-poll");
+        let data = directory.join("05_data.txt");
+        let example = directory.join("05_example.txt");
+        fs::write(&data, "seeds: 1").unwrap();
+
+        let mut watcher = Watcher::default();
+        let paths = [data.clone(), example.clone()];
+        assert_eq!(watcher.poll(&paths), paths.to_vec());
+        assert_eq!(watcher.poll(&paths), Vec::<PathBuf>::new());
+
+        fs::write(&data, "seeds: 1 2").unwrap();
+        fs::write(&example, "seeds: 3").unwrap();
+        assert_eq!(watcher.poll(&paths), paths.to_vec());
+        assert_eq!(watcher.poll(&paths[..1]), vec![example]);
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_example_files() {
+        let directory = temp_directory("watch-examples");
+        for name in ["01_data.txt", "01_example2.txt", "01_example.txt", "02_example.txt", "01_example.bak"] {
+            fs::write(directory.join(name), "").unwrap();
+        }
+
+        let examples = example_files(&InputSource::Directory(directory.clone()), 1);
+
+        assert_eq!(examples, vec![directory.join("01_example.txt"), directory.join("01_example2.txt")]);
+        assert_eq!(example_files(&InputSource::Stdin, 1), Vec::<PathBuf>::new());
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_write_run() {
+        let directory = temp_directory("watch-run");
+        fs::write(directory.join("06_data.txt"), EXAMPLES[0].input).unwrap();
+        fs::write(directory.join("06_example.txt"), "Time: 7\nDistance: 9").unwrap();
+        let source = InputSource::Directory(directory.clone());
+        let answers = Answers::parse("[day6]\npart1 = 289").unwrap();
+        let mut out = Vec::new();
+
+        write_run(&mut out, &Day06, &[Part::One], &source, Some(&answers), &example_files(&source, 6)).unwrap();
+        // drops the timings
+        let lines = String::from_utf8(out).unwrap().lines().map(|line| line.split(" (").next().unwrap().to_string() + line.split(')').nth(1).unwrap_or(""))
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec!["day 6 part 1: 288 MISMATCH, expected 289", "06_example.txt part 1: 4", "example 1 part 1: 288 ok"]);
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_write_record() {
+        let answers = Answers::parse("[day6]\npart1 = 288").unwrap();
+        let record = |part, result| Record { day: 6, part, input: String::from("-"), parse_time: Duration::ZERO, solve_time: Duration::ZERO, result };
+        let mut out = Vec::new();
+        write_record(&mut out, "day 6", &record(Part::One, Ok(289)), Some(&answers)).unwrap();
+        write_record(&mut out, "day 6", &record(Part::Two, Ok(71503)), Some(&answers)).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "day 6 part 1: 289 (parse 0.000 ms, solve 0.000 ms) MISMATCH, expected 288\n\
+                                                     day 6 part 2: 71503 (parse 0.000 ms, solve 0.000 ms)\n");
+    }
+}
@@ -0,0 +1,97 @@
+//! Golden tests: solves every registered day on its checked-in `NN_data.txt` and compares the answers with
+//! `answers.toml`. Days without an input file or expected answers are reported as ignored. The expected answers are
+//! the ones accepted on adventofcode.com, a failure after changing a solver is not fixed by recording its new output.
+//!
+//! Runs without the default test harness so that each day is its own test case, accepts a name filter
+//! like `cargo test --test golden day05`.
+
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc_2023::input::{data_file_name, InputSource};
+use aoc_2023::registry;
+use aoc_2023::runner;
+use aoc_2023::solution::{Part, Puzzle};
+use aoc_2023::verify::{Answers, Verdict};
+
+enum Outcome {
+    Passed,
+    Ignored(String),
+    Failed(Vec<String>),
+}
+
+fn check(puzzle: &dyn Puzzle, directory: &Path, answers: &Answers) -> Outcome {
+    let day = puzzle.day();
+    if !directory.join(data_file_name(day)).exists() {
+        return Outcome::Ignored(format!("no {}", data_file_name(day)));
+    }
+    if Part::ALL.iter().all(|part| answers.get(day, *part).is_none()) {
+        return Outcome::Ignored("no expected answers".to_string());
+    }
+
+    let source = InputSource::Directory(directory.to_path_buf());
+    let records = match panic::catch_unwind(panic::AssertUnwindSafe(|| runner::run(puzzle, &Part::ALL, &source))) {
+        Ok(records) => records,
+        Err(_) => return Outcome::Failed(vec!["panicked".to_string()]),
+    };
+
+    let failures: Vec<String> = records.iter().filter_map(|record| match Verdict::of(record, answers) {
+        Verdict::Match | Verdict::Unverified => None,
+        Verdict::Mismatch => Some(format!("part {}: expected {}, got {}", record.part,
+                                          answers.get(day, record.part).unwrap_or_default(), record.result.as_ref().unwrap_or(&0))),
+        Verdict::Failed(error) => Some(format!("part {}: {}", record.part, error)),
+    }).collect();
+
+    if failures.is_empty() { Outcome::Passed } else { Outcome::Failed(failures) }
+}
+
+fn main() -> ExitCode {
+    // the first argument not starting with '-' filters the test names, libtest flags are ignored
+    let filter = std::env::args().skip(1).find(|argument| !argument.starts_with('-'));
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let answers = match Answers::load(&directory.join("answers.toml")) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let puzzles: Vec<_> = registry::PUZZLES.iter()
+        .map(|puzzle| (format!("day{:02}", puzzle.day()), *puzzle))
+        .filter(|(name, _)| filter.as_ref().is_none_or(|filter| name.contains(filter.as_str())))
+        .collect();
+
+    println!("\nrunning {} test{}", puzzles.len(), if puzzles.len() == 1 { "" } else { "s" });
+    let (mut passed, mut failed, mut ignored) = (Vec::new(), Vec::new(), 0);
+    for (name, puzzle) in puzzles {
+        match check(puzzle, &directory, &answers) {
+            Outcome::Passed => {
+                println!("test {} ... ok", name);
+                passed.push(name);
+            }
+            Outcome::Ignored(reason) => {
+                println!("test {} ... ignored, {}", name, reason);
+                ignored += 1;
+            }
+            Outcome::Failed(failures) => {
+                println!("test {} ... FAILED", name);
+                failed.push((name, failures));
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        println!("\nfailures:");
+        for (name, failures) in &failed {
+            for failure in failures {
+                println!("    {}: {}", name, failure);
+            }
+        }
+    }
+    println!("\ntest result: {}. {} passed; {} failed; {} ignored\n",
+             if failed.is_empty() { "ok" } else { "FAILED" }, passed.len(), failed.len(), ignored);
+
+    if failed.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}